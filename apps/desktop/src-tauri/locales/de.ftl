@@ -0,0 +1,61 @@
+# Tray menu
+tray-show = youtube.pub anzeigen
+tray-hide = youtube.pub ausblenden
+tray-quit = Beenden
+
+# Updates
+update-none-available = Kein Update verfügbar
+
+# Crash reports
+crash-upload-disabled = Das Hochladen von Absturzberichten ist nicht aktiviert
+crash-report-not-found = Absturzbericht nicht gefunden
+
+# Backup
+backup-unsupported-version = Sicherungsformat-Version { $version } wird nicht unterstützt
+backup-schema-too-new = Das Datenbankschema { $backup } der Sicherung ist neuer als das Schema { $current } dieser App
+backup-password-required = Diese Sicherung erfordert ein Passwort
+backup-wrong-password = Falsches Sicherungspasswort
+
+# Privacy
+privacy-invalid-token = Ungültiges oder abgelaufenes Bestätigungstoken
+
+# Shortcuts
+shortcut-invalid = Ungültiges Tastenkürzel '{ $accelerator }': { $error }
+shortcut-global-needs-modifier = Das globale Tastenkürzel '{ $accelerator }' muss Strg, Alt oder Super enthalten
+shortcut-register-failed = Das Tastenkürzel für '{ $action }' konnte nicht registriert werden (möglicherweise wird es von einer anderen Anwendung verwendet): { $error }
+shortcut-in-use = Das Tastenkürzel wird bereits von '{ $action }' verwendet
+shortcut-registered-globally = Das Tastenkürzel ist bereits als globales Tastenkürzel registriert
+
+# Proxy
+proxy-host-required = Proxy-Host ist erforderlich
+proxy-invalid = Ungültiger Proxy: { $error }
+proxy-too-many-bypass-rules = Zu viele Ausnahmeregeln (max. { $max })
+
+# DNS
+dns-custom-url-required = Ein eigener DoH-Anbieter benötigt eine URL
+dns-https-required = Der DoH-Endpunkt muss https verwenden
+dns-client-not-configured = DNS über HTTPS ist nicht konfiguriert
+dns-no-addresses = Keine Adressen für { $host } gefunden
+dns-invalid-url = Ungültige DoH-URL: { $error }
+dns-invalid-host = Ungültiger Host: { $error }
+
+# GPU
+gpu-toggle-unsupported = Die Hardwarebeschleunigung kann auf dieser Plattform nicht deaktiviert werden
+
+# Thumbnail palette
+palette-invalid-video-id = Ungültige Video-ID
+palette-invalid-image = Ungültiges Bild: { $error }
+palette-no-pixels = Ungültiges Bild: keine Pixel
+
+# Theme
+theme-invalid-accent = Ungültige Akzentfarbe: { $color }
+theme-too-many-overrides = Zu viele Überschreibungen (max. { $max })
+theme-invalid-variable = Ungültige CSS-Variable: { $name }
+theme-invalid-value = Ungültiger Wert für { $name }
+
+# Profiles
+profile-invalid-name = Ungültiger Profilname: Buchstaben, Ziffern, '-' oder '_' verwenden (max. { $max })
+profile-exists = Profil existiert bereits: { $name }
+profile-limit = Zu viele Profile (max. { $max })
+profile-delete-active = Das aktive Profil kann nicht gelöscht werden
+profile-unknown = Unbekanntes Profil: { $name }
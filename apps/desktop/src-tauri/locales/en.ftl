@@ -0,0 +1,61 @@
+# Tray menu
+tray-show = Show youtube.pub
+tray-hide = Hide youtube.pub
+tray-quit = Quit
+
+# Updates
+update-none-available = No update available
+
+# Crash reports
+crash-upload-disabled = Crash report upload is not enabled
+crash-report-not-found = Crash report not found
+
+# Backup
+backup-unsupported-version = Backup format version { $version } is not supported
+backup-schema-too-new = Backup database schema { $backup } is newer than this app's schema { $current }
+backup-password-required = This backup requires a password
+backup-wrong-password = Wrong backup password
+
+# Privacy
+privacy-invalid-token = Invalid or expired confirmation token
+
+# Shortcuts
+shortcut-invalid = Invalid shortcut '{ $accelerator }': { $error }
+shortcut-global-needs-modifier = Global shortcut '{ $accelerator }' must include Ctrl, Alt or Super
+shortcut-register-failed = Could not register shortcut for '{ $action }' (it may be in use by another application): { $error }
+shortcut-in-use = Shortcut is already used by '{ $action }'
+shortcut-registered-globally = Shortcut is already registered as a global shortcut
+
+# Proxy
+proxy-host-required = Proxy host is required
+proxy-invalid = Invalid proxy: { $error }
+proxy-too-many-bypass-rules = Too many bypass rules (max { $max })
+
+# DNS
+dns-custom-url-required = A custom DoH provider requires a URL
+dns-https-required = The DoH endpoint must use https
+dns-client-not-configured = DNS over HTTPS is not configured
+dns-no-addresses = No addresses found for { $host }
+dns-invalid-url = Invalid DoH URL: { $error }
+dns-invalid-host = Invalid host: { $error }
+
+# GPU
+gpu-toggle-unsupported = Hardware acceleration can't be turned off on this platform
+
+# Thumbnail palette
+palette-invalid-video-id = Invalid video ID
+palette-invalid-image = Invalid image: { $error }
+palette-no-pixels = Invalid image: no pixels
+
+# Theme
+theme-invalid-accent = Invalid accent color: { $color }
+theme-too-many-overrides = Too many overrides (max { $max })
+theme-invalid-variable = Invalid CSS variable: { $name }
+theme-invalid-value = Invalid value for { $name }
+
+# Profiles
+profile-invalid-name = Invalid profile name: use letters, digits, '-' or '_' (max { $max })
+profile-exists = Profile already exists: { $name }
+profile-limit = Too many profiles (max { $max })
+profile-delete-active = The active profile can't be deleted
+profile-unknown = Unknown profile: { $name }
@@ -0,0 +1,61 @@
+# Tray menu
+tray-show = Mostrar youtube.pub
+tray-hide = Ocultar youtube.pub
+tray-quit = Salir
+
+# Updates
+update-none-available = No hay ninguna actualización disponible
+
+# Crash reports
+crash-upload-disabled = El envío de informes de fallos no está activado
+crash-report-not-found = No se encontró el informe de fallos
+
+# Backup
+backup-unsupported-version = La versión { $version } del formato de copia de seguridad no es compatible
+backup-schema-too-new = El esquema de base de datos { $backup } de la copia de seguridad es más reciente que el esquema { $current } de esta aplicación
+backup-password-required = Esta copia de seguridad requiere una contraseña
+backup-wrong-password = Contraseña de copia de seguridad incorrecta
+
+# Privacy
+privacy-invalid-token = Token de confirmación no válido o caducado
+
+# Shortcuts
+shortcut-invalid = Atajo no válido '{ $accelerator }': { $error }
+shortcut-global-needs-modifier = El atajo global '{ $accelerator }' debe incluir Ctrl, Alt o Super
+shortcut-register-failed = No se pudo registrar el atajo de '{ $action }' (puede que otra aplicación lo esté usando): { $error }
+shortcut-in-use = El atajo ya lo usa '{ $action }'
+shortcut-registered-globally = El atajo ya está registrado como atajo global
+
+# Proxy
+proxy-host-required = El host del proxy es obligatorio
+proxy-invalid = Proxy no válido: { $error }
+proxy-too-many-bypass-rules = Demasiadas reglas de exclusión (máx. { $max })
+
+# DNS
+dns-custom-url-required = Un proveedor DoH personalizado requiere una URL
+dns-https-required = El endpoint DoH debe usar https
+dns-client-not-configured = DNS sobre HTTPS no está configurado
+dns-no-addresses = No se encontraron direcciones para { $host }
+dns-invalid-url = URL de DoH no válida: { $error }
+dns-invalid-host = Host no válido: { $error }
+
+# GPU
+gpu-toggle-unsupported = La aceleración por hardware no se puede desactivar en esta plataforma
+
+# Thumbnail palette
+palette-invalid-video-id = ID de vídeo no válido
+palette-invalid-image = Imagen no válida: { $error }
+palette-no-pixels = Imagen no válida: no tiene píxeles
+
+# Theme
+theme-invalid-accent = Color de acento no válido: { $color }
+theme-too-many-overrides = Demasiadas personalizaciones (máx. { $max })
+theme-invalid-variable = Variable CSS no válida: { $name }
+theme-invalid-value = Valor no válido para { $name }
+
+# Profiles
+profile-invalid-name = Nombre de perfil no válido: usa letras, dígitos, '-' o '_' (máx. { $max })
+profile-exists = El perfil ya existe: { $name }
+profile-limit = Demasiados perfiles (máx. { $max })
+profile-delete-active = No se puede eliminar el perfil activo
+profile-unknown = Perfil desconocido: { $name }
@@ -0,0 +1,61 @@
+# Tray menu
+tray-show = Afficher youtube.pub
+tray-hide = Masquer youtube.pub
+tray-quit = Quitter
+
+# Updates
+update-none-available = Aucune mise à jour disponible
+
+# Crash reports
+crash-upload-disabled = L'envoi des rapports de plantage n'est pas activé
+crash-report-not-found = Rapport de plantage introuvable
+
+# Backup
+backup-unsupported-version = La version { $version } du format de sauvegarde n'est pas prise en charge
+backup-schema-too-new = Le schéma de base de données { $backup } de la sauvegarde est plus récent que le schéma { $current } de cette application
+backup-password-required = Cette sauvegarde nécessite un mot de passe
+backup-wrong-password = Mot de passe de sauvegarde incorrect
+
+# Privacy
+privacy-invalid-token = Jeton de confirmation invalide ou expiré
+
+# Shortcuts
+shortcut-invalid = Raccourci non valide « { $accelerator } » : { $error }
+shortcut-global-needs-modifier = Le raccourci global « { $accelerator } » doit inclure Ctrl, Alt ou Super
+shortcut-register-failed = Impossible d'enregistrer le raccourci pour « { $action } » (il est peut-être utilisé par une autre application) : { $error }
+shortcut-in-use = Le raccourci est déjà utilisé par « { $action } »
+shortcut-registered-globally = Le raccourci est déjà enregistré comme raccourci global
+
+# Proxy
+proxy-host-required = L'hôte du proxy est obligatoire
+proxy-invalid = Proxy non valide : { $error }
+proxy-too-many-bypass-rules = Trop de règles d'exclusion (max. { $max })
+
+# DNS
+dns-custom-url-required = Un fournisseur DoH personnalisé nécessite une URL
+dns-https-required = Le point de terminaison DoH doit utiliser https
+dns-client-not-configured = Le DNS over HTTPS n'est pas configuré
+dns-no-addresses = Aucune adresse trouvée pour { $host }
+dns-invalid-url = URL DoH non valide : { $error }
+dns-invalid-host = Hôte non valide : { $error }
+
+# GPU
+gpu-toggle-unsupported = L'accélération matérielle ne peut pas être désactivée sur cette plateforme
+
+# Thumbnail palette
+palette-invalid-video-id = ID de vidéo non valide
+palette-invalid-image = Image non valide : { $error }
+palette-no-pixels = Image non valide : aucun pixel
+
+# Theme
+theme-invalid-accent = Couleur d'accentuation non valide : { $color }
+theme-too-many-overrides = Trop de personnalisations (max. { $max })
+theme-invalid-variable = Variable CSS non valide : { $name }
+theme-invalid-value = Valeur non valide pour { $name }
+
+# Profiles
+profile-invalid-name = Nom de profil non valide : utilisez des lettres, des chiffres, « - » ou « _ » (max. { $max })
+profile-exists = Le profil existe déjà : { $name }
+profile-limit = Trop de profils (max. { $max })
+profile-delete-active = Le profil actif ne peut pas être supprimé
+profile-unknown = Profil inconnu : { $name }
@@ -0,0 +1,61 @@
+# Tray menu
+tray-show = youtube.pub を表示
+tray-hide = youtube.pub を隠す
+tray-quit = 終了
+
+# Updates
+update-none-available = 利用可能なアップデートはありません
+
+# Crash reports
+crash-upload-disabled = クラッシュレポートの送信が有効になっていません
+crash-report-not-found = クラッシュレポートが見つかりません
+
+# Backup
+backup-unsupported-version = バックアップ形式のバージョン { $version } はサポートされていません
+backup-schema-too-new = バックアップのデータベーススキーマ { $backup } は、このアプリのスキーマ { $current } より新しいです
+backup-password-required = このバックアップにはパスワードが必要です
+backup-wrong-password = バックアップのパスワードが正しくありません
+
+# Privacy
+privacy-invalid-token = 確認トークンが無効か、有効期限が切れています
+
+# Shortcuts
+shortcut-invalid = 無効なショートカット「{ $accelerator }」: { $error }
+shortcut-global-needs-modifier = グローバルショートカット「{ $accelerator }」には Ctrl、Alt、Super のいずれかを含める必要があります
+shortcut-register-failed = 「{ $action }」のショートカットを登録できませんでした（他のアプリケーションが使用している可能性があります）: { $error }
+shortcut-in-use = このショートカットは「{ $action }」で使用されています
+shortcut-registered-globally = このショートカットはすでにグローバルショートカットとして登録されています
+
+# Proxy
+proxy-host-required = プロキシのホストを入力してください
+proxy-invalid = 無効なプロキシ: { $error }
+proxy-too-many-bypass-rules = 除外ルールが多すぎます（最大 { $max } 件）
+
+# DNS
+dns-custom-url-required = カスタム DoH プロバイダーには URL が必要です
+dns-https-required = DoH エンドポイントは https を使用する必要があります
+dns-client-not-configured = DNS over HTTPS が設定されていません
+dns-no-addresses = { $host } のアドレスが見つかりません
+dns-invalid-url = 無効な DoH URL: { $error }
+dns-invalid-host = 無効なホスト: { $error }
+
+# GPU
+gpu-toggle-unsupported = このプラットフォームではハードウェアアクセラレーションを無効にできません
+
+# Thumbnail palette
+palette-invalid-video-id = 無効な動画 ID
+palette-invalid-image = 無効な画像: { $error }
+palette-no-pixels = 無効な画像: ピクセルがありません
+
+# Theme
+theme-invalid-accent = 無効なアクセントカラー: { $color }
+theme-too-many-overrides = カスタマイズが多すぎます（最大 { $max } 件）
+theme-invalid-variable = 無効な CSS 変数: { $name }
+theme-invalid-value = { $name } の値が無効です
+
+# Profiles
+profile-invalid-name = 無効なプロファイル名です。英数字、「-」、「_」を使用してください（最大 { $max } 文字）
+profile-exists = プロファイルはすでに存在します: { $name }
+profile-limit = プロファイルが多すぎます（最大 { $max } 件）
+profile-delete-active = 使用中のプロファイルは削除できません
+profile-unknown = 不明なプロファイル: { $name }
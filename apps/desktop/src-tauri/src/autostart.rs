@@ -0,0 +1,88 @@
+/// Autostart Module
+///
+/// Launch-on-login through the autostart plugin (macOS launch agent, Windows
+/// Run registry key, XDG autostart entry on Linux), plus background mode: when
+/// launched at login with "start minimized" on, the main window stays hidden in
+/// the tray until the user opens it.
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_autostart::ManagerExt;
+use tauri_plugin_store::StoreExt;
+
+/// Argument passed by the login item so a login launch can be told apart
+pub const AUTOSTART_ARG: &str = "--autostart";
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field: start hidden in the tray when launched at login
+const START_MINIMIZED_FIELD: &str = "start_minimized";
+
+/// Launch-on-login settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutostartSettings {
+    pub enabled: bool,
+    pub start_minimized: bool,
+}
+
+/// Whether this process was started by the login item
+pub fn launched_at_login() -> bool {
+    std::env::args().any(|arg| arg == AUTOSTART_ARG)
+}
+
+fn start_minimized<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(START_MINIMIZED_FIELD))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Whether the main window should stay hidden on this launch
+pub fn start_in_background<R: Runtime>(app: &AppHandle<R>) -> bool {
+    launched_at_login() && start_minimized(app)
+}
+
+/// Current launch-on-login settings
+pub fn settings<R: Runtime>(app: &AppHandle<R>) -> AutostartSettings {
+    AutostartSettings {
+        enabled: app.autolaunch().is_enabled().unwrap_or(false),
+        start_minimized: start_minimized(app),
+    }
+}
+
+/// Register or remove the login item and store the start-minimized choice
+pub fn apply<R: Runtime>(
+    app: &AppHandle<R>,
+    enabled: bool,
+    start_minimized: bool,
+) -> Result<(), String> {
+    let autolaunch = app.autolaunch();
+    if enabled {
+        autolaunch.enable().map_err(|e| e.to_string())?;
+    } else if autolaunch.is_enabled().unwrap_or(false) {
+        autolaunch.disable().map_err(|e| e.to_string())?;
+    }
+
+    let store = app.store(SETTINGS_STORE_NAME).map_err(|e| e.to_string())?;
+    store.set(START_MINIMIZED_FIELD, start_minimized);
+    store.save().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn autostart_set(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+    start_minimized: bool,
+) -> Result<(), String> {
+    apply(&app_handle, enabled, start_minimized)
+}
+
+#[tauri::command]
+pub async fn autostart_get(app_handle: tauri::AppHandle) -> Result<AutostartSettings, String> {
+    Ok(settings(&app_handle))
+}
@@ -0,0 +1,580 @@
+/// Backup Module
+///
+/// Creates and restores a single zip archive of the user's data: the gallery
+/// database, the settings/license/window-state stores, saved thumbnails and,
+/// optionally, an export of secure storage (password-protected with
+/// PBKDF2-SHA256 + AES-256-GCM when a password is given).
+///
+/// The database is open while the app runs, so a restore is staged in
+/// `pending_restore/` and moved into place by `apply_pending_restore` at the
+/// next launch, before anything opens it.
+use aes_gcm::{
+    aead::{Aead, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose, Engine as _};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::i18n::{tr, tr_args};
+
+/// Event emitted as files are written or extracted
+pub const BACKUP_PROGRESS_EVENT: &str = "backup-progress";
+
+/// Archive layout version written by this build
+const BACKUP_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_NAME: &str = "manifest.json";
+const SECRETS_NAME: &str = "secrets.json";
+/// Gallery database opened by the frontend through the SQL plugin
+pub const DATABASE_NAME: &str = "gallery.db";
+/// Stores kept in the app data directory
+pub const STORE_FILES: &[&str] = &["settings.json", "license.json", "window-state.json"];
+/// Saved thumbnail projects in the app data directory
+pub const THUMBNAILS_DIR: &str = "thumbnails";
+/// Restore staged for the next launch, in the app data directory
+const PENDING_RESTORE_DIR: &str = "pending_restore";
+
+/// PBKDF2 iterations for password-protected secrets
+const PBKDF2_ROUNDS: u32 = 600_000;
+
+/// Custom error type for backup operations
+#[derive(Debug)]
+pub enum BackupError {
+    InvalidArchive(String),
+    UnsupportedVersion(u32),
+    SchemaTooNew { backup: i64, current: i64 },
+    PasswordRequired,
+    WrongPassword,
+    StorageError(String),
+    IoError(std::io::Error),
+}
+
+impl fmt::Display for BackupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackupError::InvalidArchive(msg) => write!(f, "Invalid backup archive: {}", msg),
+            BackupError::UnsupportedVersion(version) => write!(
+                f,
+                "{}",
+                tr_args(
+                    "backup-unsupported-version",
+                    &[("version", (*version).into())]
+                )
+            ),
+            BackupError::SchemaTooNew { backup, current } => write!(
+                f,
+                "{}",
+                tr_args(
+                    "backup-schema-too-new",
+                    &[("backup", (*backup).into()), ("current", (*current).into())]
+                )
+            ),
+            BackupError::PasswordRequired => write!(f, "{}", tr("backup-password-required")),
+            BackupError::WrongPassword => write!(f, "{}", tr("backup-wrong-password")),
+            BackupError::StorageError(msg) => write!(f, "Storage error: {}", msg),
+            BackupError::IoError(err) => write!(f, "IO error: {}", err),
+        }
+    }
+}
+
+impl Error for BackupError {}
+
+impl From<std::io::Error> for BackupError {
+    fn from(err: std::io::Error) -> Self {
+        BackupError::IoError(err)
+    }
+}
+
+impl From<zip::result::ZipError> for BackupError {
+    fn from(err: zip::result::ZipError) -> Self {
+        BackupError::InvalidArchive(err.to_string())
+    }
+}
+
+impl From<rusqlite::Error> for BackupError {
+    fn from(err: rusqlite::Error) -> Self {
+        BackupError::StorageError(err.to_string())
+    }
+}
+
+/// Result type for backup operations
+pub type BackupResult<T> = Result<T, BackupError>;
+
+/// Describes an archive; written first so restore can check it up front
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupManifest {
+    pub format_version: u32,
+    pub app_version: String,
+    /// Unix timestamp in seconds
+    pub created_at: u64,
+    /// `PRAGMA user_version` of the backed-up database
+    pub database_schema: Option<i64>,
+    pub includes_secrets: bool,
+    pub secrets_protected: bool,
+}
+
+/// Secure storage export
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum SecretsExport {
+    Plain {
+        entries: BTreeMap<String, String>,
+    },
+    Protected {
+        salt: String,
+        nonce: String,
+        ciphertext: String,
+    },
+}
+
+/// Payload of `backup-progress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupProgress {
+    /// `"create"` or `"restore"`
+    pub operation: String,
+    pub current: u64,
+    pub total: u64,
+}
+
+/// Directories holding the data covered by a backup
+struct DataPaths {
+    data_dir: PathBuf,
+    /// The SQL plugin keeps databases in the app config directory
+    config_dir: PathBuf,
+    cache_dir: PathBuf,
+}
+
+impl DataPaths {
+    fn resolve<R: Runtime>(app: &AppHandle<R>) -> BackupResult<Self> {
+        let path = app.path();
+        let to_error = |e: tauri::Error| BackupError::StorageError(e.to_string());
+        Ok(Self {
+            data_dir: path.app_data_dir().map_err(to_error)?,
+            config_dir: path.app_config_dir().map_err(to_error)?,
+            cache_dir: path.app_cache_dir().map_err(to_error)?,
+        })
+    }
+}
+
+/// Recursively list files under `dir`
+pub fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> std::io::Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn database_schema(path: &Path) -> BackupResult<i64> {
+    let connection = rusqlite::Connection::open(path)?;
+    Ok(connection.pragma_query_value(None, "user_version", |row| row.get(0))?)
+}
+
+fn derive_key(password: &str, salt: &[u8]) -> Key<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, PBKDF2_ROUNDS, &mut key);
+    key.into()
+}
+
+fn export_secrets(password: Option<&str>) -> BackupResult<SecretsExport> {
+    let storage = crate::secure_storage::get_secure_storage()
+        .ok_or_else(|| BackupError::StorageError("Secure storage not initialized".to_string()))?;
+
+    let mut entries = BTreeMap::new();
+    for key in storage
+        .list_keys()
+        .map_err(|e| BackupError::StorageError(e.to_string()))?
+    {
+        if let Some(value) = storage
+            .retrieve(&key)
+            .map_err(|e| BackupError::StorageError(e.to_string()))?
+        {
+            entries.insert(key, value);
+        }
+    }
+
+    let Some(password) = password else {
+        return Ok(SecretsExport::Plain { entries });
+    };
+
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut salt);
+    OsRng.fill_bytes(&mut nonce);
+
+    let plaintext =
+        serde_json::to_vec(&entries).map_err(|e| BackupError::StorageError(e.to_string()))?;
+    let ciphertext = Aes256Gcm::new(&derive_key(password, &salt))
+        .encrypt(nonce.as_slice().into(), plaintext.as_slice())
+        .map_err(|e| BackupError::StorageError(format!("Encryption failed: {}", e)))?;
+
+    Ok(SecretsExport::Protected {
+        salt: general_purpose::STANDARD.encode(salt),
+        nonce: general_purpose::STANDARD.encode(nonce),
+        ciphertext: general_purpose::STANDARD.encode(ciphertext),
+    })
+}
+
+fn decrypt_secrets(
+    export: SecretsExport,
+    password: Option<&str>,
+) -> BackupResult<BTreeMap<String, String>> {
+    let (salt, nonce, ciphertext) = match export {
+        SecretsExport::Plain { entries } => return Ok(entries),
+        SecretsExport::Protected {
+            salt,
+            nonce,
+            ciphertext,
+        } => (salt, nonce, ciphertext),
+    };
+    let password = password.ok_or(BackupError::PasswordRequired)?;
+
+    let decode = |value: &str| {
+        general_purpose::STANDARD
+            .decode(value)
+            .map_err(|e| BackupError::InvalidArchive(e.to_string()))
+    };
+    let salt = decode(&salt)?;
+    let nonce = decode(&nonce)?;
+    let ciphertext = decode(&ciphertext)?;
+    if nonce.len() != 12 {
+        return Err(BackupError::InvalidArchive("Invalid nonce".to_string()));
+    }
+
+    #[allow(deprecated)]
+    let nonce = Nonce::from_slice(&nonce);
+    let plaintext = Aes256Gcm::new(&derive_key(password, &salt))
+        .decrypt(nonce, ciphertext.as_slice())
+        .map_err(|_| BackupError::WrongPassword)?;
+
+    serde_json::from_slice(&plaintext).map_err(|e| BackupError::InvalidArchive(e.to_string()))
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, operation: &str, current: u64, total: u64) {
+    let _ = app.emit(
+        BACKUP_PROGRESS_EVENT,
+        BackupProgress {
+            operation: operation.to_string(),
+            current,
+            total,
+        },
+    );
+}
+
+/// Write a backup archive
+///
+/// # Arguments
+/// * `app` - App handle used to resolve data directories and emit progress
+/// * `path` - Destination `.zip` path
+/// * `include_secrets` - Whether to export secure storage
+/// * `password` - Encrypts the secure storage export when given
+pub fn create<R: Runtime>(
+    app: &AppHandle<R>,
+    path: &Path,
+    include_secrets: bool,
+    password: Option<&str>,
+) -> BackupResult<BackupManifest> {
+    let paths = DataPaths::resolve(app)?;
+
+    // Snapshot the live database; VACUUM INTO is consistent under WAL
+    let database_path = paths.config_dir.join(DATABASE_NAME);
+    let snapshot_path = paths.cache_dir.join("backup-snapshot.db");
+    let database_schema = if database_path.exists() {
+        fs::create_dir_all(&paths.cache_dir)?;
+        let _ = fs::remove_file(&snapshot_path);
+        let connection = rusqlite::Connection::open(&database_path)?;
+        connection.execute("VACUUM INTO ?1", [snapshot_path.to_string_lossy().as_ref()])?;
+        Some(database_schema(&snapshot_path)?)
+    } else {
+        None
+    };
+
+    let mut entries: Vec<(String, PathBuf)> = Vec::new();
+    if database_schema.is_some() {
+        entries.push((DATABASE_NAME.to_string(), snapshot_path.clone()));
+    }
+    for name in STORE_FILES {
+        let store_path = paths.data_dir.join(name);
+        if store_path.exists() {
+            entries.push((name.to_string(), store_path));
+        }
+    }
+    let thumbnails_dir = paths.data_dir.join(THUMBNAILS_DIR);
+    let mut thumbnail_files = Vec::new();
+    collect_files(&thumbnails_dir, &mut thumbnail_files)?;
+    for file in thumbnail_files {
+        if let Ok(relative) = file.strip_prefix(&paths.data_dir) {
+            let name = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join("/");
+            entries.push((name, file));
+        }
+    }
+
+    let secrets = if include_secrets {
+        Some(export_secrets(password)?)
+    } else {
+        None
+    };
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+        database_schema,
+        includes_secrets: secrets.is_some(),
+        secrets_protected: matches!(secrets, Some(SecretsExport::Protected { .. })),
+    };
+
+    // Write to a temporary file so a failed backup never leaves a partial archive
+    let temp_path = path.with_extension("zip.partial");
+    let result = (|| -> BackupResult<()> {
+        let mut zip = zip::ZipWriter::new(fs::File::create(&temp_path)?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        let to_error = |e: serde_json::Error| BackupError::StorageError(e.to_string());
+
+        zip.start_file(MANIFEST_NAME, options)?;
+        zip.write_all(&serde_json::to_vec_pretty(&manifest).map_err(to_error)?)?;
+        if let Some(secrets) = &secrets {
+            zip.start_file(SECRETS_NAME, options)?;
+            zip.write_all(&serde_json::to_vec(secrets).map_err(to_error)?)?;
+        }
+
+        let total = entries.len() as u64;
+        for (index, (name, source)) in entries.iter().enumerate() {
+            zip.start_file(name.as_str(), options)?;
+            std::io::copy(&mut fs::File::open(source)?, &mut zip)?;
+            emit_progress(app, "create", index as u64 + 1, total);
+        }
+
+        zip.finish()?;
+        Ok(())
+    })();
+
+    let _ = fs::remove_file(&snapshot_path);
+    match result {
+        Ok(()) => {
+            fs::rename(&temp_path, path)?;
+            Ok(manifest)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+/// Stage a backup for restore and import its secrets
+///
+/// Files are extracted to `pending_restore/` and applied on the next launch;
+/// the caller should restart the app afterwards.
+///
+/// # Arguments
+/// * `app` - App handle used to resolve data directories and emit progress
+/// * `path` - Backup archive to restore
+/// * `password` - Password for a protected secure storage export
+pub fn restore<R: Runtime>(
+    app: &AppHandle<R>,
+    path: &Path,
+    password: Option<&str>,
+) -> BackupResult<BackupManifest> {
+    let paths = DataPaths::resolve(app)?;
+    let mut archive = zip::ZipArchive::new(fs::File::open(path)?)?;
+
+    let manifest: BackupManifest = {
+        let mut file = archive.by_name(MANIFEST_NAME)?;
+        let mut body = Vec::new();
+        file.read_to_end(&mut body)?;
+        serde_json::from_slice(&body).map_err(|e| BackupError::InvalidArchive(e.to_string()))?
+    };
+
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(BackupError::UnsupportedVersion(manifest.format_version));
+    }
+    let current_database = paths.config_dir.join(DATABASE_NAME);
+    if let (Some(backup), true) = (manifest.database_schema, current_database.exists()) {
+        let current = database_schema(&current_database)?;
+        if backup > current {
+            return Err(BackupError::SchemaTooNew { backup, current });
+        }
+    }
+
+    // Decrypt secrets before touching anything, so a wrong password changes nothing
+    let secrets = if manifest.includes_secrets {
+        let mut file = archive.by_name(SECRETS_NAME)?;
+        let mut body = Vec::new();
+        file.read_to_end(&mut body)?;
+        let export: SecretsExport = serde_json::from_slice(&body)
+            .map_err(|e| BackupError::InvalidArchive(e.to_string()))?;
+        Some(decrypt_secrets(export, password)?)
+    } else {
+        None
+    };
+
+    let pending_dir = paths.data_dir.join(PENDING_RESTORE_DIR);
+    if pending_dir.exists() {
+        fs::remove_dir_all(&pending_dir)?;
+    }
+    fs::create_dir_all(&pending_dir)?;
+
+    let total = archive.len() as u64;
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        // enclosed_name rejects absolute paths and `..` components
+        let Some(name) = file.enclosed_name() else {
+            continue;
+        };
+        let name_str = name.to_string_lossy().replace('\\', "/");
+        let wanted = name_str == DATABASE_NAME
+            || STORE_FILES.contains(&name_str.as_str())
+            || name_str.starts_with(&format!("{}/", THUMBNAILS_DIR));
+        if !wanted || file.is_dir() {
+            continue;
+        }
+
+        let target = pending_dir.join(&name);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        std::io::copy(&mut file, &mut fs::File::create(&target)?)?;
+        emit_progress(app, "restore", index as u64 + 1, total);
+    }
+
+    if let Some(secrets) = secrets {
+        let storage = crate::secure_storage::get_secure_storage().ok_or_else(|| {
+            BackupError::StorageError("Secure storage not initialized".to_string())
+        })?;
+        for (key, value) in secrets {
+            storage
+                .store(&key, &value)
+                .map_err(|e| BackupError::StorageError(e.to_string()))?;
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Move a staged restore into place
+///
+/// Must run at startup before the stores are read or the database is opened.
+pub fn apply_pending_restore<R: Runtime>(app: &AppHandle<R>) -> BackupResult<bool> {
+    let paths = DataPaths::resolve(app)?;
+    let pending_dir = paths.data_dir.join(PENDING_RESTORE_DIR);
+    if !pending_dir.exists() {
+        return Ok(false);
+    }
+
+    let staged_database = pending_dir.join(DATABASE_NAME);
+    if staged_database.exists() {
+        fs::create_dir_all(&paths.config_dir)?;
+        let database_path = paths.config_dir.join(DATABASE_NAME);
+        for suffix in ["-wal", "-shm"] {
+            let _ = fs::remove_file(
+                paths
+                    .config_dir
+                    .join(format!("{}{}", DATABASE_NAME, suffix)),
+            );
+        }
+        // The config dir may be on another filesystem, so copy instead of rename
+        fs::copy(&staged_database, &database_path)?;
+    }
+
+    for name in STORE_FILES {
+        let staged = pending_dir.join(name);
+        if staged.exists() {
+            fs::rename(&staged, paths.data_dir.join(name))?;
+        }
+    }
+
+    let staged_thumbnails = pending_dir.join(THUMBNAILS_DIR);
+    if staged_thumbnails.exists() {
+        let thumbnails_dir = paths.data_dir.join(THUMBNAILS_DIR);
+        if thumbnails_dir.exists() {
+            fs::remove_dir_all(&thumbnails_dir)?;
+        }
+        fs::rename(&staged_thumbnails, &thumbnails_dir)?;
+    }
+
+    fs::remove_dir_all(&pending_dir)?;
+    Ok(true)
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn backup_create(
+    app_handle: tauri::AppHandle,
+    path: String,
+    include_secrets: bool,
+    password: Option<String>,
+) -> Result<BackupManifest, String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(&path, "path", 4096)
+        .map_err(|e| format!("Invalid path: {}", e))?;
+    if let Some(password) = &password {
+        crate::security::validate_user_input(password, "password", 1024)
+            .map_err(|e| format!("Invalid password: {}", e))?;
+    }
+
+    let password = password.filter(|p| !p.is_empty());
+    tauri::async_runtime::spawn_blocking(move || {
+        create(
+            &app_handle,
+            Path::new(&path),
+            include_secrets,
+            password.as_deref(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn backup_restore(
+    app_handle: tauri::AppHandle,
+    path: String,
+    password: Option<String>,
+) -> Result<BackupManifest, String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(&path, "path", 4096)
+        .map_err(|e| format!("Invalid path: {}", e))?;
+    if let Some(password) = &password {
+        crate::security::validate_user_input(password, "password", 1024)
+            .map_err(|e| format!("Invalid password: {}", e))?;
+    }
+
+    let password = password.filter(|p| !p.is_empty());
+    // The frontend relaunches the app afterwards so the staged files are applied
+    tauri::async_runtime::spawn_blocking(move || {
+        restore(&app_handle, Path::new(&path), password.as_deref())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
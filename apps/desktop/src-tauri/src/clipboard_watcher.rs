@@ -0,0 +1,136 @@
+/// Clipboard Watcher Module
+///
+/// Opt-in background monitor that polls the clipboard for copied YouTube links
+/// and emits `clipboard-video-detected` events. It is disabled by default and
+/// never reads the clipboard unless the user has turned it on.
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+use tauri_plugin_store::StoreExt;
+
+use crate::url_router::NavigateTarget;
+
+/// Event emitted when a YouTube link is copied
+pub const CLIPBOARD_VIDEO_DETECTED_EVENT: &str = "clipboard-video-detected";
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field enabling the watcher
+const CLIPBOARD_WATCHER_FIELD: &str = "clipboard_watcher_enabled";
+
+/// Interval between clipboard reads
+const POLL_INTERVAL_MS: u64 = 1000;
+
+/// The same link is not reported again within this window
+const DEBOUNCE_SECS: u64 = 30;
+
+/// Payload of `clipboard-video-detected`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardVideoDetected {
+    pub url: String,
+    pub target: NavigateTarget,
+}
+
+/// Whether the watcher should be running
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Incremented on every start so a stale polling task exits
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Start the watcher if the user has enabled it
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let enabled = app
+        .store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(CLIPBOARD_WATCHER_FIELD))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false);
+
+    set_enabled(app, enabled);
+}
+
+/// Start or stop the polling task
+pub fn set_enabled<R: Runtime>(app: &AppHandle<R>, enabled: bool) {
+    ENABLED.store(enabled, Ordering::SeqCst);
+    let generation = GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
+
+    if enabled {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            poll(app, generation).await;
+        });
+    }
+}
+
+/// Store the setting and start or stop the watcher to match
+pub fn save_enabled<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> Result<(), String> {
+    let store = app.store(SETTINGS_STORE_NAME).map_err(|e| e.to_string())?;
+    store.set(CLIPBOARD_WATCHER_FIELD, enabled);
+    store.save().map_err(|e| e.to_string())?;
+
+    set_enabled(app, enabled);
+    Ok(())
+}
+
+/// Whether the watcher is currently enabled
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::SeqCst)
+}
+
+async fn poll<R: Runtime>(app: AppHandle<R>, generation: u64) {
+    // Whatever is on the clipboard when the watcher starts was not "just copied"
+    let mut last_text = app.clipboard().read_text().ok();
+    let mut last_emitted: Option<(NavigateTarget, Instant)> = None;
+
+    while ENABLED.load(Ordering::SeqCst) && GENERATION.load(Ordering::SeqCst) == generation {
+        tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+
+        let Ok(text) = app.clipboard().read_text() else {
+            continue;
+        };
+        if last_text.as_deref() == Some(text.as_str()) {
+            continue;
+        }
+        last_text = Some(text.clone());
+
+        let Some(target) = crate::url_router::parse(&text) else {
+            continue;
+        };
+
+        let duplicate = matches!(
+            &last_emitted,
+            Some((previous, at))
+                if *previous == target && at.elapsed() < Duration::from_secs(DEBOUNCE_SECS)
+        );
+        if duplicate {
+            continue;
+        }
+
+        last_emitted = Some((target.clone(), Instant::now()));
+        let _ = app.emit(
+            CLIPBOARD_VIDEO_DETECTED_EVENT,
+            ClipboardVideoDetected {
+                url: text.trim().to_string(),
+                target,
+            },
+        );
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn clipboard_watcher_set_enabled(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    save_enabled(&app_handle, enabled)
+}
+
+#[tauri::command]
+pub async fn clipboard_watcher_enabled(_app_handle: tauri::AppHandle) -> Result<bool, String> {
+    Ok(is_enabled())
+}
@@ -0,0 +1,305 @@
+/// Crash Reports Module
+///
+/// Captures Rust panics to local JSON reports (message, location, backtrace,
+/// app and OS version) with secrets scrubbed by `logging::redact`. Reports
+/// stay on disk until the user deletes them; they are only uploaded when the
+/// user has explicitly consented and configured an endpoint.
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding `CrashReportSettings`
+const CRASH_REPORT_SETTINGS_FIELD: &str = "crash_report_settings";
+
+/// Reports kept on disk; older ones are removed when a new one is written
+const MAX_REPORTS: usize = 20;
+
+/// A captured crash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReport {
+    pub id: String,
+    /// Unix timestamp in seconds
+    pub timestamp: u64,
+    pub app_version: String,
+    pub os: String,
+    pub arch: String,
+    pub thread: Option<String>,
+    pub message: String,
+    pub location: Option<String>,
+    pub backtrace: String,
+    /// Whether the report has been sent to the configured endpoint
+    pub uploaded: bool,
+}
+
+/// Upload consent and destination
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CrashReportSettings {
+    /// The user explicitly agreed to send reports
+    pub upload_consent: bool,
+    pub endpoint: String,
+}
+
+/// Directory reports are written to
+static REPORTS_DIR: once_cell::sync::OnceCell<PathBuf> = once_cell::sync::OnceCell::new();
+
+/// Install the panic hook
+///
+/// Runs before the Tauri builder so panics during setup are captured too; the
+/// previous hook still runs afterwards, so panics are printed as before.
+///
+/// # Arguments
+/// * `identifier` - App identifier; reports go in `crash_reports/` in the same
+///   `app_data_dir` Tauri resolves (the platform data directory joined with it)
+pub fn init(identifier: &str) -> std::io::Result<()> {
+    let dir = dirs::data_dir()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "No data directory"))?
+        .join(identifier)
+        .join("crash_reports");
+    std::fs::create_dir_all(&dir)?;
+    let _ = REPORTS_DIR.set(dir);
+
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Unknown panic".to_string());
+        let location = info
+            .location()
+            .map(|l| format!("{}:{}:{}", l.file(), l.line(), l.column()));
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+
+        let report = CrashReport {
+            id: new_report_id(),
+            timestamp: now_secs(),
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            thread: std::thread::current().name().map(|name| name.to_string()),
+            message: crate::logging::redact(&message),
+            location,
+            backtrace: crate::logging::redact(&backtrace),
+            uploaded: false,
+        };
+        let _ = write_report(&report);
+
+        previous(info);
+    }));
+
+    Ok(())
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn new_report_id() -> String {
+    let mut random = [0u8; 4];
+    rand::thread_rng().fill_bytes(&mut random);
+    format!("{}-{}", now_secs(), hex::encode(random))
+}
+
+/// Report IDs are generated by us; reject anything else to keep paths inside the dir
+fn valid_report_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= 64 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+}
+
+fn report_path(id: &str) -> Option<PathBuf> {
+    if !valid_report_id(id) {
+        return None;
+    }
+    REPORTS_DIR
+        .get()
+        .map(|dir| dir.join(format!("{}.json", id)))
+}
+
+fn write_report(report: &CrashReport) -> std::io::Result<()> {
+    let path = report_path(&report.id)
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "No reports dir"))?;
+    let body = serde_json::to_vec_pretty(report)?;
+    std::fs::write(path, body)?;
+
+    // Keep only the newest reports
+    let reports = list();
+    for old in reports.iter().skip(MAX_REPORTS) {
+        delete(&old.id);
+    }
+    Ok(())
+}
+
+/// All reports, newest first
+pub fn list() -> Vec<CrashReport> {
+    let Some(dir) = REPORTS_DIR.get() else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut reports: Vec<CrashReport> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .map(|e| e == "json")
+                .unwrap_or(false)
+        })
+        .filter_map(|entry| std::fs::read(entry.path()).ok())
+        .filter_map(|bytes| serde_json::from_slice(&bytes).ok())
+        .collect();
+    reports.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    reports
+}
+
+/// Delete a report
+///
+/// # Returns
+/// * `true` if the report existed
+pub fn delete(id: &str) -> bool {
+    match report_path(id) {
+        Some(path) if path.exists() => std::fs::remove_file(path).is_ok(),
+        _ => false,
+    }
+}
+
+/// Load the stored settings, falling back to no consent
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> CrashReportSettings {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(CRASH_REPORT_SETTINGS_FIELD))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Send one report to the configured endpoint
+///
+/// # Returns
+/// * `Err(String)` if consent is missing, the report doesn't exist or the upload failed
+pub async fn upload<R: Runtime>(app: &AppHandle<R>, id: &str) -> Result<(), String> {
+    let settings = load_settings(app);
+    if !settings.upload_consent || settings.endpoint.is_empty() {
+        return Err(crate::i18n::tr("crash-upload-disabled"));
+    }
+
+    let mut report = list()
+        .into_iter()
+        .find(|report| report.id == id)
+        .ok_or_else(|| crate::i18n::tr("crash-report-not-found"))?;
+
+    let client = crate::http_client::get_http_client().ok_or("HTTP client not initialized")?;
+    let request = client
+        .client_for("crash_reports")
+        .post(&settings.endpoint)
+        .json(&report);
+    let response = client.send(request).await.map_err(|e| e.to_string())?;
+    if !response.status().is_success() {
+        return Err(format!("Upload failed with status {}", response.status()));
+    }
+
+    report.uploaded = true;
+    write_report(&report).map_err(|e| e.to_string())
+}
+
+/// Upload every pending report if the user has consented
+pub async fn upload_pending<R: Runtime>(app: AppHandle<R>) {
+    if !load_settings(&app).upload_consent {
+        return;
+    }
+
+    for report in list().into_iter().filter(|report| !report.uploaded) {
+        if let Err(e) = upload(&app, &report.id).await {
+            tracing::warn!("Failed to upload crash report {}: {}", report.id, e);
+            break;
+        }
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn crash_reports_list(_app_handle: tauri::AppHandle) -> Result<Vec<CrashReport>, String> {
+    Ok(list())
+}
+
+#[tauri::command]
+pub async fn crash_reports_delete(
+    _app_handle: tauri::AppHandle,
+    id: String,
+) -> Result<bool, String> {
+    // SECURITY: Validate input parameters
+    if id == "all" {
+        let reports = list();
+        let count = reports.len();
+        for report in reports {
+            delete(&report.id);
+        }
+        return Ok(count > 0);
+    }
+    if !valid_report_id(&id) {
+        return Err("Invalid crash report ID".to_string());
+    }
+
+    Ok(delete(&id))
+}
+
+#[tauri::command]
+pub async fn crash_reports_upload(app_handle: tauri::AppHandle, id: String) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    if !valid_report_id(&id) {
+        return Err("Invalid crash report ID".to_string());
+    }
+
+    upload(&app_handle, &id).await
+}
+
+#[tauri::command]
+pub async fn crash_reports_get_settings(
+    app_handle: tauri::AppHandle,
+) -> Result<CrashReportSettings, String> {
+    Ok(load_settings(&app_handle))
+}
+
+#[tauri::command]
+pub async fn crash_reports_set_settings(
+    app_handle: tauri::AppHandle,
+    settings: CrashReportSettings,
+) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(
+        &settings.endpoint,
+        "endpoint",
+        crate::security::MAX_URL_LENGTH,
+    )
+    .map_err(|e| format!("Invalid endpoint: {}", e))?;
+    if !settings.endpoint.is_empty() {
+        let url =
+            url::Url::parse(&settings.endpoint).map_err(|e| format!("Invalid endpoint: {}", e))?;
+        if url.scheme() != "https" {
+            return Err("Invalid endpoint: must be an https URL".to_string());
+        }
+    }
+
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    store.set(
+        CRASH_REPORT_SETTINGS_FIELD,
+        serde_json::to_value(&settings).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
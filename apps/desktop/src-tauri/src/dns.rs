@@ -0,0 +1,500 @@
+/// DNS Resolver Module
+///
+/// Opt-in DNS-over-HTTPS resolver installed into the shared HTTP client. When
+/// enabled, lookups for YouTube hosts (or every host) are sent to the chosen
+/// DoH provider instead of the local network's resolver, falling back to
+/// system DNS only if the provider can't be reached (a "no such host" answer
+/// is final). Recent lookups are recorded so a
+/// diagnostics command can show which resolver answered.
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri_plugin_store::StoreExt;
+use tracing::Instrument;
+
+use crate::i18n::{tr, tr_args};
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding the serialized `DohSettings`
+const DOH_SETTINGS_FIELD: &str = "doh_settings";
+
+/// Domains resolved over DoH when `all_hosts` is off
+const PROTECTED_DOMAINS: &[&str] = &[
+    "youtube.com",
+    "youtu.be",
+    "googlevideo.com",
+    "ytimg.com",
+    "ggpht.com",
+    "googleusercontent.com",
+    "youtube-nocookie.com",
+];
+
+/// Number of recent lookups kept for diagnostics
+const MAX_RECENT_LOOKUPS: usize = 64;
+
+/// Timeout for a single DoH query
+const DOH_TIMEOUT_SECS: u64 = 5;
+
+/// Lower bound on how long DoH answers are cached
+const MIN_ANSWER_TTL_SECS: u64 = 30;
+
+/// Most DoH answers kept in the cache
+const MAX_CACHED_ANSWERS: usize = 512;
+
+/// DNS-over-HTTPS provider
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DohProvider {
+    Cloudflare,
+    Google,
+    Quad9,
+    Custom,
+}
+
+impl DohProvider {
+    /// JSON API endpoint and bootstrap address for the built-in providers
+    fn endpoint(&self) -> Option<(&'static str, &'static str, SocketAddr)> {
+        match self {
+            DohProvider::Cloudflare => Some((
+                "https://cloudflare-dns.com/dns-query",
+                "cloudflare-dns.com",
+                SocketAddr::from(([1, 1, 1, 1], 443)),
+            )),
+            DohProvider::Google => Some((
+                "https://dns.google/resolve",
+                "dns.google",
+                SocketAddr::from(([8, 8, 8, 8], 443)),
+            )),
+            DohProvider::Quad9 => Some((
+                "https://dns.quad9.net:5053/dns-query",
+                "dns.quad9.net",
+                SocketAddr::from(([9, 9, 9, 9], 5053)),
+            )),
+            DohProvider::Custom => None,
+        }
+    }
+}
+
+/// User-facing DoH configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DohSettings {
+    pub enabled: bool,
+    pub provider: DohProvider,
+    /// JSON API endpoint used when `provider` is `custom`
+    pub custom_url: Option<String>,
+    /// Resolve every host over DoH, not only YouTube/Google video hosts
+    pub all_hosts: bool,
+}
+
+impl Default for DohSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider: DohProvider::Cloudflare,
+            custom_url: None,
+            all_hosts: false,
+        }
+    }
+}
+
+/// Which resolver produced an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolvedBy {
+    Doh,
+    DohCache,
+    System,
+    SystemFallback,
+}
+
+/// One recorded lookup
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LookupRecord {
+    pub host: String,
+    pub resolved_by: ResolvedBy,
+    pub addresses: Vec<String>,
+    /// Error from the DoH provider when falling back to system DNS
+    pub doh_error: Option<String>,
+    /// Unix time in seconds
+    pub at: u64,
+}
+
+/// Diagnostics returned by `dns_diagnostics`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnsDiagnostics {
+    pub settings: DohSettings,
+    pub recent: Vec<LookupRecord>,
+    /// Result of resolving the requested host, if one was given
+    pub probe: Option<LookupRecord>,
+}
+
+#[derive(Deserialize)]
+struct DohResponse {
+    #[serde(rename = "Status")]
+    status: u32,
+    #[serde(rename = "Answer", default)]
+    answer: Vec<DohAnswer>,
+}
+
+#[derive(Deserialize)]
+struct DohAnswer {
+    #[serde(rename = "type")]
+    record_type: u16,
+    #[serde(rename = "TTL", default)]
+    ttl: u64,
+    data: String,
+}
+
+struct ResolverState {
+    settings: RwLock<DohSettings>,
+    /// Client used to talk to the DoH provider, rebuilt with the settings
+    client: RwLock<Option<reqwest::Client>>,
+    answers: Mutex<HashMap<String, (Vec<IpAddr>, Instant)>>,
+    recent: Mutex<VecDeque<LookupRecord>>,
+}
+
+/// Resolver installed into the shared HTTP client
+#[derive(Clone)]
+pub struct DohResolver {
+    state: Arc<ResolverState>,
+}
+
+impl DohResolver {
+    fn new() -> Self {
+        Self {
+            state: Arc::new(ResolverState {
+                settings: RwLock::new(DohSettings::default()),
+                client: RwLock::new(None),
+                answers: Mutex::new(HashMap::new()),
+                recent: Mutex::new(VecDeque::new()),
+            }),
+        }
+    }
+
+    /// Replace the active settings
+    ///
+    /// # Arguments
+    /// * `settings` - New settings
+    /// * `base` - Builder for the DoH client, already routed through the proxy
+    ///
+    /// # Returns
+    /// * `Ok(())` if the settings are usable
+    /// * `Err(String)` if the custom endpoint is invalid or the client fails to build
+    pub fn configure(
+        &self,
+        settings: DohSettings,
+        base: reqwest::ClientBuilder,
+    ) -> Result<(), String> {
+        let client = if settings.enabled {
+            let mut builder = base
+                .timeout(Duration::from_secs(DOH_TIMEOUT_SECS))
+                .https_only(true);
+
+            // Pin the provider's address so the DoH lookup itself doesn't leak
+            match settings.provider.endpoint() {
+                Some((_, host, bootstrap)) => builder = builder.resolve(host, bootstrap),
+                None => {
+                    let url = settings
+                        .custom_url
+                        .as_deref()
+                        .ok_or_else(|| tr("dns-custom-url-required"))?;
+                    let parsed = url::Url::parse(url).map_err(|e| e.to_string())?;
+                    if parsed.scheme() != "https" {
+                        return Err(tr("dns-https-required"));
+                    }
+                }
+            }
+
+            Some(builder.build().map_err(|e| e.to_string())?)
+        } else {
+            None
+        };
+
+        *self.state.client.write().unwrap() = client;
+        *self.state.settings.write().unwrap() = settings;
+        self.state.answers.lock().unwrap().clear();
+        Ok(())
+    }
+
+    /// Current settings
+    pub fn settings(&self) -> DohSettings {
+        self.state.settings.read().unwrap().clone()
+    }
+
+    /// Recent lookups, newest first
+    pub fn recent(&self) -> Vec<LookupRecord> {
+        self.state
+            .recent
+            .lock()
+            .unwrap()
+            .iter()
+            .rev()
+            .cloned()
+            .collect()
+    }
+
+    /// Resolve a host, recording which resolver answered
+    pub async fn lookup(&self, host: &str) -> Result<LookupRecord, String> {
+        let settings = self.settings();
+        let use_doh = settings.enabled && (settings.all_hosts || is_protected(host));
+
+        let mut doh_error = None;
+        if use_doh {
+            if let Some(addresses) = self.cached_answer(host) {
+                return Ok(self.record(host, ResolvedBy::DohCache, addresses, None));
+            }
+
+            match self.query_doh(&settings, host).await {
+                Ok(addresses) if !addresses.is_empty() => {
+                    return Ok(self.record(host, ResolvedBy::Doh, addresses, None));
+                }
+                // An empty answer or NXDOMAIN is authoritative; asking the
+                // system resolver would leak the host DoH is meant to protect
+                Ok(_) => {
+                    self.record(host, ResolvedBy::Doh, Vec::new(), None);
+                    return Err(tr_args("dns-no-addresses", &[("host", host.into())]));
+                }
+                // Only transport failures fall back to system DNS
+                Err(e) => doh_error = Some(e),
+            }
+        }
+
+        let addresses: Vec<IpAddr> = tokio::net::lookup_host((host, 0))
+            .await
+            .map_err(|e| e.to_string())?
+            .map(|addr| addr.ip())
+            .collect();
+
+        let resolved_by = if use_doh {
+            ResolvedBy::SystemFallback
+        } else {
+            ResolvedBy::System
+        };
+        Ok(self.record(host, resolved_by, addresses, doh_error))
+    }
+
+    fn cached_answer(&self, host: &str) -> Option<Vec<IpAddr>> {
+        let answers = self.state.answers.lock().unwrap();
+        answers
+            .get(host)
+            .filter(|(_, expires)| *expires > Instant::now())
+            .map(|(addresses, _)| addresses.clone())
+    }
+
+    /// Query A and AAAA records from the DoH provider
+    async fn query_doh(&self, settings: &DohSettings, host: &str) -> Result<Vec<IpAddr>, String> {
+        let client = self
+            .state
+            .client
+            .read()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| tr("dns-client-not-configured"))?;
+        let endpoint = match settings.provider.endpoint() {
+            Some((url, _, _)) => url.to_string(),
+            None => settings.custom_url.clone().unwrap_or_default(),
+        };
+
+        let mut addresses = Vec::new();
+        let mut min_ttl = u64::MAX;
+
+        for record_type in ["A", "AAAA"] {
+            let response: DohResponse = client
+                .get(&endpoint)
+                .query(&[("name", host), ("type", record_type)])
+                .header(reqwest::header::ACCEPT, "application/dns-json")
+                .send()
+                .await
+                .map_err(|e| e.to_string())?
+                .error_for_status()
+                .map_err(|e| e.to_string())?
+                .json()
+                .await
+                .map_err(|e| e.to_string())?;
+
+            // NXDOMAIN and friends are answers, not transport failures
+            if response.status != 0 {
+                continue;
+            }
+
+            for answer in response.answer {
+                if answer.record_type == 1 || answer.record_type == 28 {
+                    if let Ok(ip) = answer.data.parse::<IpAddr>() {
+                        addresses.push(ip);
+                        min_ttl = min_ttl.min(answer.ttl);
+                    }
+                }
+            }
+        }
+
+        if !addresses.is_empty() {
+            let ttl = Duration::from_secs(min_ttl.max(MIN_ANSWER_TTL_SECS));
+            let now = Instant::now();
+            let mut answers = self.state.answers.lock().unwrap();
+
+            // Drop expired answers, then the one expiring soonest if still full
+            answers.retain(|_, (_, expires)| *expires > now);
+            if answers.len() >= MAX_CACHED_ANSWERS {
+                let soonest = answers
+                    .iter()
+                    .min_by_key(|(_, (_, expires))| *expires)
+                    .map(|(host, _)| host.clone());
+                if let Some(soonest) = soonest {
+                    answers.remove(&soonest);
+                }
+            }
+            answers.insert(host.to_string(), (addresses.clone(), now + ttl));
+        }
+
+        Ok(addresses)
+    }
+
+    fn record(
+        &self,
+        host: &str,
+        resolved_by: ResolvedBy,
+        addresses: Vec<IpAddr>,
+        doh_error: Option<String>,
+    ) -> LookupRecord {
+        let record = LookupRecord {
+            host: host.to_string(),
+            resolved_by,
+            addresses: addresses.iter().map(|ip| ip.to_string()).collect(),
+            doh_error,
+            at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+        };
+
+        let mut recent = self.state.recent.lock().unwrap();
+        recent.push_back(record.clone());
+        while recent.len() > MAX_RECENT_LOOKUPS {
+            recent.pop_front();
+        }
+
+        record
+    }
+}
+
+impl Resolve for DohResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let resolver = self.clone();
+        Box::pin(async move {
+            let record = resolver
+                .lookup(name.as_str())
+                .instrument(tracing::info_span!("dns.resolve", host = name.as_str()))
+                .await
+                .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { e.into() })?;
+            let addrs: Addrs = Box::new(
+                record
+                    .addresses
+                    .into_iter()
+                    .filter_map(|ip| ip.parse::<IpAddr>().ok())
+                    .map(|ip| SocketAddr::new(ip, 0)),
+            );
+            Ok(addrs)
+        })
+    }
+}
+
+/// Whether a host belongs to one of the protected domains
+fn is_protected(host: &str) -> bool {
+    PROTECTED_DOMAINS
+        .iter()
+        .any(|domain| host == *domain || host.ends_with(&format!(".{}", domain)))
+}
+
+/// Global resolver instance shared by every client
+static RESOLVER: once_cell::sync::Lazy<DohResolver> = once_cell::sync::Lazy::new(DohResolver::new);
+
+/// Get the resolver to install into HTTP clients
+pub fn resolver() -> Arc<DohResolver> {
+    Arc::new(RESOLVER.clone())
+}
+
+/// Load the stored DoH settings, falling back to defaults
+pub fn load_settings(app_handle: &tauri::AppHandle) -> DohSettings {
+    app_handle
+        .store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(DOH_SETTINGS_FIELD))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Apply the stored DoH settings to the global resolver
+pub fn apply_stored_settings(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let base = crate::proxy::configure_builder(app_handle, reqwest::Client::builder())?;
+    RESOLVER.configure(load_settings(app_handle), base)
+}
+
+/// Rebuild the DoH client for new proxy settings, keeping the DoH settings
+pub fn reconfigure(
+    proxy: &crate::proxy::ProxySettings,
+    proxy_password: Option<&str>,
+) -> Result<(), String> {
+    let base = crate::proxy::configure_client(reqwest::Client::builder(), proxy, proxy_password)?;
+    RESOLVER.configure(RESOLVER.settings(), base)
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn dns_get_settings(app_handle: tauri::AppHandle) -> Result<DohSettings, String> {
+    Ok(load_settings(&app_handle))
+}
+
+#[tauri::command]
+pub async fn dns_set_settings(
+    app_handle: tauri::AppHandle,
+    settings: DohSettings,
+) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    if let Some(url) = &settings.custom_url {
+        crate::security::validate_user_input(url, "DoH URL", crate::security::MAX_URL_LENGTH)
+            .map_err(|e| tr_args("dns-invalid-url", &[("error", e.to_string().into())]))?;
+    }
+
+    // Validate by applying before persisting
+    let base = crate::proxy::configure_builder(&app_handle, reqwest::Client::builder())?;
+    RESOLVER.configure(settings.clone(), base)?;
+
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    store.set(
+        DOH_SETTINGS_FIELD,
+        serde_json::to_value(&settings).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn dns_diagnostics(
+    _app_handle: tauri::AppHandle,
+    host: Option<String>,
+) -> Result<DnsDiagnostics, String> {
+    let probe = match host {
+        Some(host) => {
+            // SECURITY: Validate input parameters
+            crate::security::validate_user_input(&host, "host", 253)
+                .map_err(|e| tr_args("dns-invalid-host", &[("error", e.to_string().into())]))?;
+            Some(RESOLVER.lookup(host.trim()).await?)
+        }
+        None => None,
+    };
+
+    Ok(DnsDiagnostics {
+        settings: RESOLVER.settings(),
+        recent: RESOLVER.recent(),
+        probe,
+    })
+}
@@ -0,0 +1,176 @@
+/// First Run Module
+///
+/// Detects a fresh install (no settings, gallery database or saved thumbnails
+/// yet) and runs the onboarding pipeline: seed default settings, apply the
+/// choices made in onboarding, and mark onboarding as done, reporting progress
+/// through `first-run-progress`. Installs that predate onboarding are marked
+/// done without showing it.
+///
+/// Importing existing data goes through `backup_restore`; the gallery schema
+/// is created by the frontend when it opens the database.
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::backup::{DATABASE_NAME, STORE_FILES, THUMBNAILS_DIR};
+use crate::theme::AppTheme;
+
+/// Event emitted as onboarding steps complete
+pub const FIRST_RUN_PROGRESS_EVENT: &str = "first-run-progress";
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field set once onboarding is finished or skipped
+const FIRST_RUN_COMPLETED_FIELD: &str = "first_run_completed";
+
+/// Settings written on a fresh install unless already present; these match
+/// the defaults the frontend assumes
+const DEFAULT_SETTINGS: &[(&str, bool)] = &[
+    ("show_december_snow", true),
+    ("close_to_tray", false),
+    ("minimize_to_tray", false),
+    ("clipboard_watcher_enabled", false),
+    ("hardware_acceleration", true),
+];
+
+/// Whether this launch started with no existing profile data
+static FRESH_INSTALL: once_cell::sync::OnceCell<bool> = once_cell::sync::OnceCell::new();
+
+/// Onboarding state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirstRunState {
+    /// No profile data existed when the app started
+    pub fresh_install: bool,
+    /// Onboarding should be shown
+    pub pending: bool,
+}
+
+/// Choices made during onboarding
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirstRunOptions {
+    pub theme: Option<AppTheme>,
+    pub launch_at_login: bool,
+    pub clipboard_watcher: bool,
+}
+
+/// Payload of `first-run-progress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FirstRunProgress {
+    pub step: String,
+    pub current: u64,
+    pub total: u64,
+}
+
+fn is_completed<R: Runtime>(app: &AppHandle<R>) -> bool {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(FIRST_RUN_COMPLETED_FIELD))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+fn mark_completed<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
+    let store = app.store(SETTINGS_STORE_NAME).map_err(|e| e.to_string())?;
+    store.set(FIRST_RUN_COMPLETED_FIELD, true);
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Detect a fresh install
+///
+/// Must run before anything writes to the profile, i.e. right after a pending
+/// backup restore is applied.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let path = app.path();
+    let (Ok(data_dir), Ok(config_dir)) = (path.app_data_dir(), path.app_config_dir()) else {
+        return;
+    };
+
+    let has_data = STORE_FILES.iter().any(|name| data_dir.join(name).exists())
+        || config_dir.join(DATABASE_NAME).exists()
+        || data_dir.join(THUMBNAILS_DIR).exists();
+    let _ = FRESH_INSTALL.set(!has_data);
+
+    // Existing installs never saw onboarding; don't show it to them now
+    if has_data && !is_completed(app) {
+        if let Err(e) = mark_completed(app) {
+            tracing::warn!("Failed to mark onboarding as completed: {}", e);
+        }
+    }
+}
+
+/// Current onboarding state
+pub fn state<R: Runtime>(app: &AppHandle<R>) -> FirstRunState {
+    FirstRunState {
+        fresh_install: FRESH_INSTALL.get().copied().unwrap_or(false),
+        pending: !is_completed(app),
+    }
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, step: &str, current: u64, total: u64) {
+    let _ = app.emit(
+        FIRST_RUN_PROGRESS_EVENT,
+        FirstRunProgress {
+            step: step.to_string(),
+            current,
+            total,
+        },
+    );
+}
+
+/// Seed defaults, apply onboarding choices and mark onboarding as done
+pub fn complete<R: Runtime>(app: &AppHandle<R>, options: FirstRunOptions) -> Result<(), String> {
+    const TOTAL_STEPS: u64 = 4;
+
+    emit_progress(app, "settings", 0, TOTAL_STEPS);
+    let store = app.store(SETTINGS_STORE_NAME).map_err(|e| e.to_string())?;
+    for (field, value) in DEFAULT_SETTINGS {
+        if store.get(field).is_none() {
+            store.set(*field, *value);
+        }
+    }
+    store.save().map_err(|e| e.to_string())?;
+
+    emit_progress(app, "appearance", 1, TOTAL_STEPS);
+    if let Some(theme) = options.theme {
+        let mut settings = crate::theme::load_settings(app);
+        settings.theme = theme;
+        crate::theme::save_settings(app, settings)?;
+    }
+
+    emit_progress(app, "startup", 2, TOTAL_STEPS);
+    if options.launch_at_login {
+        crate::autostart::apply(app, true, false)?;
+    }
+    if options.clipboard_watcher {
+        crate::clipboard_watcher::save_enabled(app, true)?;
+    }
+
+    emit_progress(app, "finish", 3, TOTAL_STEPS);
+    mark_completed(app)?;
+
+    emit_progress(app, "done", TOTAL_STEPS, TOTAL_STEPS);
+    Ok(())
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn first_run_state(app_handle: tauri::AppHandle) -> Result<FirstRunState, String> {
+    Ok(state(&app_handle))
+}
+
+#[tauri::command]
+pub async fn first_run_complete(
+    app_handle: tauri::AppHandle,
+    options: FirstRunOptions,
+) -> Result<(), String> {
+    complete(&app_handle, options)
+}
+
+#[tauri::command]
+pub async fn first_run_skip(app_handle: tauri::AppHandle) -> Result<(), String> {
+    mark_completed(&app_handle)
+}
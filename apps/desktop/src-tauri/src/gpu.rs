@@ -0,0 +1,143 @@
+/// GPU Module
+///
+/// Hardware acceleration toggle and GPU diagnostics for troubleshooting
+/// rendering problems. The toggle is persisted in the settings store and
+/// applied at the next launch through the webview's environment (WebView2
+/// browser arguments on Windows, WebKitGTK variables on Linux), since it has to
+/// be in place before the first webview is created. WKWebView on macOS has no
+/// equivalent switch, so the toggle is reported as unsupported there.
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri_plugin_store::StoreExt;
+
+/// Whether the webview on this platform can be switched to software rendering
+const TOGGLE_SUPPORTED: bool = cfg!(any(target_os = "windows", target_os = "linux"));
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field: allow GPU acceleration in the webview
+const HARDWARE_ACCELERATION_FIELD: &str = "hardware_acceleration";
+
+/// Whether acceleration was disabled when this process started
+static DISABLED_AT_LAUNCH: AtomicBool = AtomicBool::new(false);
+
+/// GPU state for troubleshooting reports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GpuDiagnostics {
+    /// Whether acceleration can be turned off on this platform
+    pub toggle_supported: bool,
+    /// The stored setting, which takes effect at the next launch
+    pub hardware_acceleration: bool,
+    /// Whether acceleration is enabled in this process
+    pub active: bool,
+    /// The setting differs from what this process started with
+    pub restart_required: bool,
+    /// Adapters with their driver, where the platform reports it
+    pub adapters: Vec<String>,
+    pub hardware_decoders: Option<Vec<String>>,
+    pub webview_version: Option<String>,
+}
+
+/// Same location the store plugin resolves `settings.json` to: `app_data_dir`
+/// is the platform data directory joined with the app identifier
+fn settings_path(identifier: &str) -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(identifier).join(SETTINGS_STORE_NAME))
+}
+
+/// Read the stored toggle straight from disk, defaulting to enabled
+fn stored_setting(identifier: &str) -> bool {
+    settings_path(identifier)
+        .and_then(|path| std::fs::read(path).ok())
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+        .and_then(|settings| settings[HARDWARE_ACCELERATION_FIELD].as_bool())
+        .unwrap_or(true)
+}
+
+/// Set an environment variable unless the user already has
+#[cfg(target_os = "linux")]
+fn set_env_default(key: &str, value: &str) {
+    if std::env::var_os(key).is_none() {
+        std::env::set_var(key, value);
+    }
+}
+
+/// Apply the stored toggle to the webview environment
+///
+/// Must run before the Tauri builder creates any webview.
+///
+/// # Arguments
+/// * `identifier` - App identifier from the Tauri config, which names the data dir
+pub fn apply_launch_flags(identifier: &str) {
+    if !TOGGLE_SUPPORTED || stored_setting(identifier) {
+        return;
+    }
+    DISABLED_AT_LAUNCH.store(true, Ordering::SeqCst);
+
+    #[cfg(target_os = "windows")]
+    {
+        let args = match std::env::var("WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS") {
+            Ok(existing) if !existing.is_empty() => format!("{} --disable-gpu", existing),
+            _ => "--disable-gpu".to_string(),
+        };
+        std::env::set_var("WEBVIEW2_ADDITIONAL_BROWSER_ARGUMENTS", args);
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        set_env_default("WEBKIT_DISABLE_COMPOSITING_MODE", "1");
+        set_env_default("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn gpu_acceleration_set(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<bool, String> {
+    if !TOGGLE_SUPPORTED {
+        return Err(crate::i18n::tr("gpu-toggle-unsupported"));
+    }
+
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    store.set(HARDWARE_ACCELERATION_FIELD, enabled);
+    store.save().map_err(|e| e.to_string())?;
+
+    // Whether a restart is needed for the change to apply
+    Ok(enabled == DISABLED_AT_LAUNCH.load(Ordering::SeqCst))
+}
+
+#[tauri::command]
+pub async fn gpu_diagnostics(app_handle: tauri::AppHandle) -> Result<GpuDiagnostics, String> {
+    let hardware_acceleration = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(HARDWARE_ACCELERATION_FIELD))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(true);
+    let active = !DISABLED_AT_LAUNCH.load(Ordering::SeqCst);
+
+    let (adapters, hardware_decoders) = tauri::async_runtime::spawn_blocking(|| {
+        (
+            crate::system_report::gpus(),
+            crate::system_report::hardware_decoders(),
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(GpuDiagnostics {
+        toggle_supported: TOGGLE_SUPPORTED,
+        hardware_acceleration,
+        active,
+        restart_required: TOGGLE_SUPPORTED && hardware_acceleration != active,
+        adapters,
+        hardware_decoders,
+        webview_version: tauri::webview_version().ok(),
+    })
+}
@@ -0,0 +1,522 @@
+/// HTTP Client Module
+///
+/// Shared HTTP client wrapper used by every network module in the backend.
+/// Every request goes through a retry middleware with exponential backoff and
+/// per-host circuit breakers, and fails fast while the network is offline.
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Timeout applied to every outbound request
+const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Retries attempted after the first try for transient failures
+const MAX_RETRIES: u32 = 3;
+
+/// Base delay for exponential backoff
+const BACKOFF_BASE_MS: u64 = 500;
+
+/// Upper bound for a single backoff delay
+const BACKOFF_MAX_MS: u64 = 10_000;
+
+/// Longest Retry-After the middleware will honor before giving up
+const MAX_RETRY_AFTER_SECS: u64 = 60;
+
+/// Consecutive failures that open a host's circuit
+const CIRCUIT_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long an open circuit rejects requests before letting a trial through
+const CIRCUIT_OPEN_SECS: u64 = 30;
+
+/// Redirects followed before a request fails
+const MAX_REDIRECTS: usize = 10;
+
+/// Check run on every redirect hop, given the request's original URL and the
+/// URL it is being redirected to; an `Err` stops the redirect
+pub type RedirectGuard = fn(&url::Url, &url::Url) -> Result<(), String>;
+
+/// Guards registered by modules that restrict where their requests may go
+static REDIRECT_GUARDS: RwLock<Vec<RedirectGuard>> = RwLock::new(Vec::new());
+
+/// Custom error type for HTTP operations
+#[derive(Debug)]
+pub enum HttpError {
+    InvalidUrl(String),
+    RequestFailed(String),
+    Status(u16),
+    InitError(String),
+    CircuitOpen(String),
+    Offline,
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpError::InvalidUrl(msg) => write!(f, "Invalid URL: {}", msg),
+            HttpError::RequestFailed(msg) => write!(f, "Request failed: {}", msg),
+            HttpError::Status(code) => write!(f, "Unexpected HTTP status: {}", code),
+            HttpError::InitError(msg) => write!(f, "Initialization error: {}", msg),
+            HttpError::CircuitOpen(host) => {
+                write!(f, "Too many failures, requests to {} paused", host)
+            }
+            HttpError::Offline => write!(f, "Network is offline"),
+        }
+    }
+}
+
+impl Error for HttpError {}
+
+impl From<reqwest::Error> for HttpError {
+    fn from(err: reqwest::Error) -> Self {
+        HttpError::RequestFailed(err.to_string())
+    }
+}
+
+/// Result type for HTTP operations
+pub type HttpResult<T> = Result<T, HttpError>;
+
+/// Circuit breaker state for a single host
+#[derive(Debug, Default)]
+struct HostCircuit {
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    /// Half-open: the single request allowed through after the cool-down
+    /// hasn't finished yet
+    trial_in_flight: bool,
+    total_failures: u64,
+}
+
+/// Counters kept by the retry middleware
+#[derive(Debug, Default)]
+struct Counters {
+    requests: AtomicU64,
+    retries: AtomicU64,
+    failures: AtomicU64,
+    circuit_rejections: AtomicU64,
+}
+
+/// Per-host view of the circuit breakers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HostStats {
+    pub host: String,
+    pub consecutive_failures: u32,
+    pub total_failures: u64,
+    pub circuit_open: bool,
+}
+
+/// Snapshot of network counters returned by `network_stats`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkStats {
+    /// Requests sent over the network, including retries
+    pub requests: u64,
+    /// Attempts repeated after a transient failure
+    pub retries: u64,
+    /// Requests that still failed after all retries
+    pub failures: u64,
+    /// Requests rejected because the host's circuit was open
+    pub circuit_rejections: u64,
+    pub hosts: Vec<HostStats>,
+}
+
+/// Shared HTTP client with retries and circuit breakers
+pub struct HttpClient {
+    /// Connection-pooled client routed through the configured proxy
+    client: RwLock<reqwest::Client>,
+    /// Client that always connects directly, for bypassed modules
+    direct: reqwest::Client,
+    /// Modules that use the direct client
+    bypass_modules: RwLock<Vec<String>>,
+    /// Circuit breaker state keyed by host
+    circuits: Mutex<HashMap<String, HostCircuit>>,
+    /// Middleware counters
+    counters: Counters,
+    /// Set by the network monitor while connectivity is down
+    offline: AtomicBool,
+}
+
+impl HttpClient {
+    /// Create the client
+    ///
+    /// # Returns
+    /// * `Ok(HttpClient)` if initialization succeeds
+    /// * `Err(HttpError)` if the underlying clients cannot be built
+    pub fn new() -> HttpResult<Self> {
+        let client = Self::base_builder().build()?;
+        let direct = Self::base_builder().no_proxy().build()?;
+
+        Ok(Self {
+            client: RwLock::new(client),
+            direct,
+            bypass_modules: RwLock::new(Vec::new()),
+            circuits: Mutex::new(HashMap::new()),
+            counters: Counters::default(),
+            offline: AtomicBool::new(false),
+        })
+    }
+
+    /// Builder with the settings shared by every client
+    fn base_builder() -> reqwest::ClientBuilder {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .user_agent(concat!("youtube.pub/", env!("CARGO_PKG_VERSION")))
+            .dns_resolver(crate::dns::resolver())
+            .redirect(reqwest::redirect::Policy::custom(Self::check_redirect))
+    }
+
+    /// Redirect policy shared by every client: a hop limit plus the
+    /// registered guards
+    fn check_redirect(attempt: reqwest::redirect::Attempt) -> reqwest::redirect::Action {
+        let verdict = if attempt.previous().len() >= MAX_REDIRECTS {
+            Err("Too many redirects".to_string())
+        } else {
+            let origin = &attempt.previous()[0];
+            REDIRECT_GUARDS
+                .read()
+                .unwrap()
+                .iter()
+                .try_for_each(|guard| guard(origin, attempt.url()))
+        };
+
+        match verdict {
+            Ok(()) => attempt.follow(),
+            Err(e) => attempt.error(e),
+        }
+    }
+
+    /// Rebuild the proxied client from new proxy settings
+    ///
+    /// # Arguments
+    /// * `settings` - Proxy settings to apply
+    /// * `password` - Proxy password, if any
+    pub fn apply_proxy(
+        &self,
+        settings: &crate::proxy::ProxySettings,
+        password: Option<&str>,
+    ) -> HttpResult<()> {
+        let client = crate::proxy::configure_client(Self::base_builder(), settings, password)
+            .map_err(HttpError::InvalidUrl)?
+            .build()?;
+
+        *self.client.write().unwrap() = client;
+        *self.bypass_modules.write().unwrap() = settings.bypass_modules.clone();
+        Ok(())
+    }
+
+    /// Client to build requests with for a given backend module
+    ///
+    /// Requests must still be sent through `send` to get retries.
+    pub fn client_for(&self, module: &str) -> reqwest::Client {
+        let bypassed = self
+            .bypass_modules
+            .read()
+            .unwrap()
+            .iter()
+            .any(|m| m == module);
+        if bypassed {
+            self.direct.clone()
+        } else {
+            self.client.read().unwrap().clone()
+        }
+    }
+
+    /// Send a request through the retry middleware
+    ///
+    /// Connection errors, 429 and 502/503/504 responses (plus 500 for
+    /// idempotent methods) are retried with exponential backoff and full
+    /// jitter, honoring Retry-After when present. Hosts that keep failing,
+    /// server errors included, have their circuit opened for a cool-down period
+    /// during which requests fail fast with `HttpError::CircuitOpen`; after it,
+    /// a single trial request decides whether the circuit closes again.
+    ///
+    /// # Arguments
+    /// * `request` - Request to send; it must be cloneable (no streaming body)
+    ///
+    /// # Returns
+    /// * `Ok(reqwest::Response)` with the final response, whatever its status
+    /// * `Err(HttpError)` on transport failure or an open circuit
+    #[tracing::instrument(
+        name = "http.send",
+        skip_all,
+        fields(host = tracing::field::Empty, method = tracing::field::Empty)
+    )]
+    pub async fn send(&self, request: reqwest::RequestBuilder) -> HttpResult<reqwest::Response> {
+        let probe = request
+            .try_clone()
+            .ok_or_else(|| HttpError::RequestFailed("Request body is not cloneable".to_string()))?
+            .build()?;
+        let host = probe.url().host_str().unwrap_or_default().to_string();
+        let idempotent = probe.method().is_idempotent();
+
+        let span = tracing::Span::current();
+        span.record("host", host.as_str());
+        span.record("method", probe.method().as_str());
+
+        if self.is_offline() {
+            return Err(HttpError::Offline);
+        }
+        let circuit = self.check_circuit(&host)?;
+
+        let mut attempt = 0;
+        loop {
+            let attempt_request = request.try_clone().ok_or_else(|| {
+                HttpError::RequestFailed("Request body is not cloneable".to_string())
+            })?;
+            self.counters.requests.fetch_add(1, Ordering::Relaxed);
+
+            let outcome = attempt_request.send().await;
+            let retry_delay = match &outcome {
+                Ok(response) if Self::is_retryable_status(response.status(), idempotent) => {
+                    Some(Self::retry_after(response).unwrap_or_else(|| Self::backoff(attempt)))
+                }
+                Ok(_) => None,
+                Err(e) if e.is_timeout() || e.is_connect() || e.is_request() => {
+                    Some(Self::backoff(attempt))
+                }
+                Err(_) => None,
+            };
+
+            match retry_delay {
+                Some(delay) if attempt < MAX_RETRIES && delay.as_secs() <= MAX_RETRY_AFTER_SECS => {
+                    attempt += 1;
+                    self.counters.retries.fetch_add(1, Ordering::Relaxed);
+                    tracing::debug!(%host, attempt, ?delay, "retrying request");
+                    tokio::time::sleep(delay).await;
+                }
+                Some(_) => {
+                    circuit.failure();
+                    return outcome.map_err(HttpError::from);
+                }
+                None => {
+                    match &outcome {
+                        Ok(response) if response.status().is_server_error() => circuit.failure(),
+                        Ok(_) => circuit.success(),
+                        Err(_) => circuit.failure(),
+                    }
+                    return outcome.map_err(HttpError::from);
+                }
+            }
+        }
+    }
+
+    /// Perform a GET request, failing on a non-success status
+    ///
+    /// # Arguments
+    /// * `module` - Name of the calling module, used for proxy bypass rules
+    /// * `url` - Absolute URL to fetch
+    pub async fn get(&self, module: &str, url: &str) -> HttpResult<reqwest::Response> {
+        let response = self.send(self.client_for(module).get(url)).await?;
+        if !response.status().is_success() {
+            return Err(HttpError::Status(response.status().as_u16()));
+        }
+        Ok(response)
+    }
+
+    /// Switch offline mode on or off
+    ///
+    /// While offline, `send` fails fast with `HttpError::Offline`.
+    pub fn set_offline(&self, offline: bool) {
+        self.offline.store(offline, Ordering::Relaxed);
+    }
+
+    /// Whether the client is in offline mode
+    pub fn is_offline(&self) -> bool {
+        self.offline.load(Ordering::Relaxed)
+    }
+
+    /// Snapshot the middleware counters
+    pub fn stats(&self) -> NetworkStats {
+        let circuits = self.circuits.lock().unwrap();
+        let now = Instant::now();
+
+        let mut hosts: Vec<HostStats> = circuits
+            .iter()
+            .map(|(host, circuit)| HostStats {
+                host: host.clone(),
+                consecutive_failures: circuit.consecutive_failures,
+                total_failures: circuit.total_failures,
+                circuit_open: circuit.open_until.map_or(false, |until| until > now),
+            })
+            .collect();
+        hosts.sort_by(|a, b| a.host.cmp(&b.host));
+
+        NetworkStats {
+            requests: self.counters.requests.load(Ordering::Relaxed),
+            retries: self.counters.retries.load(Ordering::Relaxed),
+            failures: self.counters.failures.load(Ordering::Relaxed),
+            circuit_rejections: self.counters.circuit_rejections.load(Ordering::Relaxed),
+            hosts,
+        }
+    }
+
+    /// Fail fast if the host's circuit is open
+    ///
+    /// The returned guard must be given the request's outcome; if the request
+    /// is dropped first, a half-open trial is released for the next request.
+    fn check_circuit<'a>(&'a self, host: &'a str) -> HttpResult<CircuitGuard<'a>> {
+        let mut circuits = self.circuits.lock().unwrap();
+        if let Some(circuit) = circuits.get_mut(host) {
+            match circuit.open_until {
+                // Open, or half-open with the trial request still running
+                Some(until) if until > Instant::now() || circuit.trial_in_flight => {
+                    self.counters
+                        .circuit_rejections
+                        .fetch_add(1, Ordering::Relaxed);
+                    return Err(HttpError::CircuitOpen(host.to_string()));
+                }
+                // Cool-down elapsed: half-open, let this request probe the host
+                Some(_) => {
+                    circuit.trial_in_flight = true;
+                    return Ok(CircuitGuard {
+                        client: self,
+                        host,
+                        trial: true,
+                    });
+                }
+                None => {}
+            }
+        }
+        Ok(CircuitGuard {
+            client: self,
+            host,
+            trial: false,
+        })
+    }
+
+    fn record_success(&self, host: &str) {
+        let mut circuits = self.circuits.lock().unwrap();
+        if let Some(circuit) = circuits.get_mut(host) {
+            circuit.consecutive_failures = 0;
+            circuit.open_until = None;
+            circuit.trial_in_flight = false;
+        }
+    }
+
+    fn record_failure(&self, host: &str) {
+        self.counters.failures.fetch_add(1, Ordering::Relaxed);
+
+        let mut circuits = self.circuits.lock().unwrap();
+        let circuit = circuits.entry(host.to_string()).or_default();
+        circuit.consecutive_failures += 1;
+        circuit.total_failures += 1;
+        // A failed trial reopens the circuit for another cool-down
+        if circuit.trial_in_flight || circuit.consecutive_failures >= CIRCUIT_FAILURE_THRESHOLD {
+            circuit.open_until = Some(Instant::now() + Duration::from_secs(CIRCUIT_OPEN_SECS));
+        }
+        circuit.trial_in_flight = false;
+    }
+
+    /// 500 may come from a request that partly ran, so it is only retried
+    /// when repeating the request is safe
+    fn is_retryable_status(status: reqwest::StatusCode, idempotent: bool) -> bool {
+        match status.as_u16() {
+            429 | 502 | 503 | 504 => true,
+            500 => idempotent,
+            _ => false,
+        }
+    }
+
+    /// Exponential backoff with full jitter for a zero-based attempt number
+    fn backoff(attempt: u32) -> Duration {
+        let ceiling = BACKOFF_BASE_MS
+            .saturating_mul(1u64 << attempt.min(16))
+            .min(BACKOFF_MAX_MS);
+        Duration::from_millis(rand::thread_rng().gen_range(0..=ceiling))
+    }
+
+    /// Delay requested by a Retry-After header, in seconds or as an HTTP date
+    fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+        let value = response
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)?
+            .to_str()
+            .ok()?
+            .trim();
+        if let Ok(seconds) = value.parse::<u64>() {
+            return Some(Duration::from_secs(seconds));
+        }
+
+        // A date in the past means "retry now"
+        let at = httpdate::parse_http_date(value).ok()?;
+        Some(at.duration_since(SystemTime::now()).unwrap_or_default())
+    }
+}
+
+/// A request admitted by `check_circuit`, waiting for its outcome
+struct CircuitGuard<'a> {
+    client: &'a HttpClient,
+    host: &'a str,
+    /// Whether this request is the half-open trial
+    trial: bool,
+}
+
+impl CircuitGuard<'_> {
+    fn success(mut self) {
+        self.trial = false;
+        self.client.record_success(self.host);
+    }
+
+    fn failure(mut self) {
+        self.trial = false;
+        self.client.record_failure(self.host);
+    }
+}
+
+impl Drop for CircuitGuard<'_> {
+    /// A cancelled trial says nothing about the host; let the next request try
+    fn drop(&mut self) {
+        if self.trial {
+            let mut circuits = self.client.circuits.lock().unwrap();
+            if let Some(circuit) = circuits.get_mut(self.host) {
+                circuit.trial_in_flight = false;
+            }
+        }
+    }
+}
+
+/// Register a check run on every redirect hop of every request
+///
+/// # Arguments
+/// * `guard` - Check to run; it should only reject redirects of requests
+///   whose original URL it is responsible for
+pub fn add_redirect_guard(guard: RedirectGuard) {
+    REDIRECT_GUARDS.write().unwrap().push(guard);
+}
+
+/// Global HTTP client instance (using OnceCell for thread safety)
+static HTTP_CLIENT: once_cell::sync::OnceCell<HttpClient> = once_cell::sync::OnceCell::new();
+
+/// Initialize the global HTTP client
+///
+/// # Returns
+/// * `Ok(())` if initialization succeeds
+/// * `Err(HttpError)` if initialization fails
+pub fn init_http_client() -> HttpResult<()> {
+    let client = HttpClient::new()?;
+    HTTP_CLIENT
+        .set(client)
+        .map_err(|_| HttpError::InitError("HTTP client already initialized".to_string()))
+}
+
+/// Get the global HTTP client instance
+///
+/// # Returns
+/// * `Some(&HttpClient)` if initialized
+/// * `None` if not initialized
+pub fn get_http_client() -> Option<&'static HttpClient> {
+    HTTP_CLIENT.get()
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn network_stats(_app_handle: tauri::AppHandle) -> Result<NetworkStats, String> {
+    // Ensure HTTP client is initialized
+    let client = get_http_client().ok_or("HTTP client not initialized")?;
+
+    Ok(client.stats())
+}
@@ -0,0 +1,274 @@
+/// I18n Module
+///
+/// Localizes backend-originated strings (tray labels, user-facing errors) with
+/// Fluent catalogs bundled as resources under `locales/<lang>.ftl`. The locale
+/// follows the OS unless the user picks one with `set_locale`; messages missing
+/// from a catalog fall back to English, which is also compiled in so the app
+/// still has text if the resources are missing.
+use fluent_bundle::concurrent::FluentBundle;
+use fluent_bundle::{FluentArgs, FluentResource, FluentValue};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::RwLock;
+use tauri::path::BaseDirectory;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+use unic_langid::LanguageIdentifier;
+
+/// Event emitted when the backend locale changes
+pub const LOCALE_CHANGED_EVENT: &str = "locale-changed";
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding the chosen locale; absent to follow the OS
+const LOCALE_FIELD: &str = "locale";
+
+/// Fallback locale, always available
+const DEFAULT_LOCALE: &str = "en";
+/// Compiled-in copy of the fallback catalog
+const DEFAULT_CATALOG: &str = include_str!("../locales/en.ftl");
+
+/// Locale state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleInfo {
+    /// Locale in use
+    pub locale: String,
+    /// Locale chosen by the user, `None` when following the OS
+    pub preferred: Option<String>,
+    /// Locales with a bundled catalog
+    pub available: Vec<String>,
+}
+
+/// Loaded catalogs
+struct Localizer {
+    locale: String,
+    /// Catalog for `locale`; `None` when that is the fallback itself
+    bundle: Option<FluentBundle<FluentResource>>,
+    fallback: FluentBundle<FluentResource>,
+}
+
+static LOCALIZER: RwLock<Option<Localizer>> = RwLock::new(None);
+
+/// Directory holding the bundled catalogs
+static LOCALES_DIR: once_cell::sync::OnceCell<PathBuf> = once_cell::sync::OnceCell::new();
+
+fn build_bundle(locale: &str, source: String) -> Option<FluentBundle<FluentResource>> {
+    let langid: LanguageIdentifier = locale.parse().ok()?;
+    let resource = match FluentResource::try_new(source) {
+        Ok(resource) => resource,
+        Err((resource, errors)) => {
+            tracing::warn!("Errors in {} catalog: {:?}", locale, errors);
+            resource
+        }
+    };
+
+    let mut bundle = FluentBundle::new_concurrent(vec![langid]);
+    // Isolation marks would show up literally in tray menus
+    bundle.set_use_isolating(false);
+    bundle.add_resource(resource).ok()?;
+    Some(bundle)
+}
+
+fn load_catalog(locale: &str) -> Option<String> {
+    let path = LOCALES_DIR.get()?.join(format!("{}.ftl", locale));
+    std::fs::read_to_string(path).ok()
+}
+
+/// Locales with a bundled catalog
+pub fn available_locales() -> Vec<String> {
+    let mut locales: Vec<String> = LOCALES_DIR
+        .get()
+        .and_then(|dir| std::fs::read_dir(dir).ok())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension().map(|e| e == "ftl").unwrap_or(false) {
+                        path.file_stem().map(|s| s.to_string_lossy().to_string())
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if !locales.iter().any(|locale| locale == DEFAULT_LOCALE) {
+        locales.push(DEFAULT_LOCALE.to_string());
+    }
+    locales.sort();
+    locales
+}
+
+/// Best available catalog for a requested locale
+///
+/// Tries the exact tag (`pt-BR`), then the language alone (`pt`).
+fn negotiate(requested: &str, available: &[String]) -> Option<String> {
+    let requested = requested.replace('_', "-");
+    let language = requested.split('-').next().unwrap_or(&requested);
+
+    available
+        .iter()
+        .find(|locale| locale.eq_ignore_ascii_case(&requested))
+        .or_else(|| {
+            available
+                .iter()
+                .find(|locale| locale.eq_ignore_ascii_case(language))
+        })
+        .cloned()
+}
+
+fn preferred_locale<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(LOCALE_FIELD))
+        .and_then(|value| value.as_str().map(|s| s.to_string()))
+}
+
+/// Load the catalogs for the chosen or OS locale
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    if let Ok(dir) = app.path().resolve("locales", BaseDirectory::Resource) {
+        let _ = LOCALES_DIR.set(dir);
+    }
+
+    let requested = preferred_locale(app)
+        .or_else(sys_locale::get_locale)
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+    activate(&requested);
+}
+
+/// Switch to the best catalog for `requested`
+///
+/// # Returns
+/// * The locale now in use
+fn activate(requested: &str) -> String {
+    let fallback_source =
+        load_catalog(DEFAULT_LOCALE).unwrap_or_else(|| DEFAULT_CATALOG.to_string());
+    let Some(fallback) = build_bundle(DEFAULT_LOCALE, fallback_source) else {
+        return current_locale();
+    };
+
+    let localized = negotiate(requested, &available_locales())
+        .filter(|locale| locale != DEFAULT_LOCALE)
+        .and_then(|locale| {
+            let bundle = build_bundle(&locale, load_catalog(&locale)?)?;
+            Some((locale, bundle))
+        });
+    let (locale, bundle) = match localized {
+        Some((locale, bundle)) => (locale, Some(bundle)),
+        None => (DEFAULT_LOCALE.to_string(), None),
+    };
+
+    *LOCALIZER.write().unwrap() = Some(Localizer {
+        locale: locale.clone(),
+        bundle,
+        fallback,
+    });
+    locale
+}
+
+/// Locale currently in use
+pub fn current_locale() -> String {
+    LOCALIZER
+        .read()
+        .unwrap()
+        .as_ref()
+        .map(|localizer| localizer.locale.clone())
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string())
+}
+
+fn format(
+    bundle: &FluentBundle<FluentResource>,
+    id: &str,
+    args: Option<&FluentArgs>,
+) -> Option<String> {
+    let pattern = bundle.get_message(id)?.value()?;
+    let mut errors = Vec::new();
+    let text = bundle
+        .format_pattern(pattern, args, &mut errors)
+        .to_string();
+    Some(text)
+}
+
+/// Localize a message with arguments
+///
+/// # Arguments
+/// * `id` - Message ID from the catalogs
+/// * `args` - Named arguments referenced as `{ $name }`
+///
+/// # Returns
+/// * The localized text, the English text if missing, or `id` as a last resort
+pub fn tr_args(id: &str, args: &[(&str, FluentValue)]) -> String {
+    let mut fluent_args = FluentArgs::new();
+    for (name, value) in args {
+        fluent_args.set(*name, value.clone());
+    }
+    let fluent_args = (!args.is_empty()).then_some(&fluent_args);
+
+    let localizer = LOCALIZER.read().unwrap();
+    match localizer.as_ref() {
+        Some(localizer) => localizer
+            .bundle
+            .as_ref()
+            .and_then(|bundle| format(bundle, id, fluent_args))
+            .or_else(|| format(&localizer.fallback, id, fluent_args))
+            .unwrap_or_else(|| id.to_string()),
+        None => build_bundle(DEFAULT_LOCALE, DEFAULT_CATALOG.to_string())
+            .and_then(|bundle| format(&bundle, id, fluent_args))
+            .unwrap_or_else(|| id.to_string()),
+    }
+}
+
+/// Localize a message without arguments
+pub fn tr(id: &str) -> String {
+    tr_args(id, &[])
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn get_locale(app_handle: tauri::AppHandle) -> Result<LocaleInfo, String> {
+    Ok(LocaleInfo {
+        locale: current_locale(),
+        preferred: preferred_locale(&app_handle),
+        available: available_locales(),
+    })
+}
+
+#[tauri::command]
+pub async fn set_locale(
+    app_handle: tauri::AppHandle,
+    locale: Option<String>,
+) -> Result<String, String> {
+    // SECURITY: Validate input parameters
+    if let Some(locale) = &locale {
+        crate::security::validate_user_input(locale, "locale", 35)
+            .map_err(|e| format!("Invalid locale: {}", e))?;
+        locale
+            .replace('_', "-")
+            .parse::<LanguageIdentifier>()
+            .map_err(|e| format!("Invalid locale: {}", e))?;
+    }
+
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    match &locale {
+        Some(locale) => store.set(LOCALE_FIELD, locale.clone()),
+        None => {
+            store.delete(LOCALE_FIELD);
+        }
+    }
+    store.save().map_err(|e| e.to_string())?;
+
+    let requested = locale
+        .or_else(sys_locale::get_locale)
+        .unwrap_or_else(|| DEFAULT_LOCALE.to_string());
+    let active = activate(&requested);
+
+    crate::tray::refresh(&app_handle);
+    let _ = app_handle.emit(LOCALE_CHANGED_EVENT, active.clone());
+    Ok(active)
+}
@@ -1,13 +1,78 @@
 use tauri::Manager;
-use tauri_plugin_decorum::WebviewWindowExt;
+use tauri_plugin_deep_link::DeepLinkExt;
 
 // Declare modules
+pub mod autostart;
+pub mod backup;
+pub mod clipboard_watcher;
+pub mod crash_reports;
+pub mod dns;
+pub mod first_run;
+pub mod gpu;
+pub mod http_client;
+pub mod i18n;
+pub mod logging;
+pub mod native_messaging;
+pub mod network_monitor;
+pub mod power;
+pub mod privacy;
+pub mod profiles;
+pub mod proxy;
 pub mod secure_storage;
 pub mod security;
+pub mod shortcuts;
+pub mod system_report;
+pub mod telemetry;
+pub mod theme;
+pub mod thumb_cache;
+pub mod thumb_palette;
+pub mod tray;
+pub mod updates;
+pub mod url_router;
+pub mod windows;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Launched by a browser extension: serve it over stdio without a window
+    let args: Vec<String> = std::env::args().collect();
+    if native_messaging::is_host_launch(&args) {
+        native_messaging::run_host();
+        return;
+    }
+
+    let context = tauri::generate_context!();
+
+    // Capture panics as crash reports, including any during setup
+    if let Err(e) = crash_reports::init(&context.config().identifier) {
+        eprintln!("Failed to initialize crash reports: {}", e);
+    }
+
+    // Must be set before the first webview is created
+    gpu::apply_launch_flags(&context.config().identifier);
+
     tauri::Builder::default()
+        // Must be registered first so a second launch is forwarded here
+        .plugin(tauri_plugin_single_instance::init(|app, argv, _cwd| {
+            tray::show_main_window(app);
+            if let Err(e) = profiles::switch_from_args(app, &argv) {
+                tracing::error!("Failed to switch profile: {}", e);
+            }
+        }))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(tauri_plugin_autostart::init(
+            tauri_plugin_autostart::MacosLauncher::LaunchAgent,
+            Some(vec![autostart::AUTOSTART_ARG]),
+        ))
+        .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(|app, shortcut, event| {
+                    if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+                        shortcuts::handle_global(app, shortcut);
+                    }
+                })
+                .build(),
+        )
         .plugin(tauri_plugin_decorum::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
@@ -16,22 +81,106 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_process::init())
+        .register_asynchronous_uri_scheme_protocol("thumb", |_ctx, request, responder| {
+            tauri::async_runtime::spawn(async move {
+                responder.respond(thumb_cache::handle_uri_request(request).await);
+            });
+        })
+        .on_window_event(|window, event| {
+            windows::handle_window_event(window, event);
+            tray::handle_window_event(window, event);
+            theme::handle_window_event(window, event);
+        })
+        .on_page_load(|webview, payload| {
+            theme::handle_page_load(webview, payload);
+            windows::handle_page_load(webview, payload);
+        })
         .setup(|app| {
-            let main_window = app.get_webview_window("main").unwrap();
-            main_window.create_overlay_titlebar().unwrap();
+            // Apply a restored backup before anything reads the data
+            if let Err(e) = backup::apply_pending_restore(app.handle()) {
+                eprintln!("Failed to apply restored backup: {}", e);
+            }
 
-            #[cfg(target_os = "macos")]
-            {
-                main_window.set_traffic_lights_inset(12.0, 16.0).unwrap();
+            // Swap in another profile's data if one was selected
+            if let Err(e) = profiles::apply_pending_switch(app.handle()) {
+                eprintln!("Failed to switch profile: {}", e);
             }
 
-            // Initialize Secure Storage
+            // Detect a fresh install before anything writes to the profile
+            first_run::init(app.handle());
+
+            // Initialize Secure Storage (the telemetry exporter needs the
+            // proxy password during logging setup)
             let app_data_dir = app.path().app_data_dir().unwrap();
             let app_name = app.package_info().name.clone();
             
             secure_storage::init_secure_storage(&app_name, &app_data_dir)
                 .expect("Failed to initialize secure storage");
 
+            // Initialize Logging
+            if let Err(e) = logging::init(app.handle()) {
+                eprintln!("Failed to initialize logging: {}", e);
+            }
+
+            // Load Backend Translations
+            i18n::init(app.handle());
+
+            windows::setup_main_window(app.handle())?;
+
+            // Initialize HTTP Client
+            http_client::init_http_client().expect("Failed to initialize HTTP client");
+
+            if let Err(e) = dns::apply_stored_settings(app.handle()) {
+                tracing::error!("Failed to apply DNS settings: {}", e);
+            }
+            if let Err(e) = proxy::apply_stored_settings(app.handle()) {
+                tracing::error!("Failed to apply proxy settings: {}", e);
+            }
+
+            // Send pending crash reports (only with consent)
+            tauri::async_runtime::spawn(crash_reports::upload_pending(app.handle().clone()));
+
+            // Create Tray Icon
+            if let Err(e) = tray::init(app.handle()) {
+                tracing::error!("Failed to create tray icon: {}", e);
+                // Without a tray a background launch could never be shown
+                tray::show_main_window(app.handle());
+            }
+
+            // Route Deep Links
+            #[cfg(any(target_os = "linux", all(debug_assertions, windows)))]
+            app.deep_link().register_all()?;
+
+            let deep_link_handle = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    url_router::route(&deep_link_handle, url.as_str());
+                }
+            });
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    url_router::route(app.handle(), url.as_str());
+                }
+            }
+
+            // Register Global Shortcuts
+            shortcuts::init(app.handle());
+
+            // Start Clipboard Watcher (opt-in)
+            clipboard_watcher::init(app.handle());
+
+            // Start Power Source Monitor
+            power::start_monitor(app.handle().clone());
+
+            // Start Network Monitor
+            network_monitor::start(app.handle().clone());
+
+            // Initialize Thumbnail Cache
+            let app_cache_dir = app.path().app_cache_dir().unwrap();
+
+            thumb_cache::init_thumb_cache(&app_cache_dir)
+                .expect("Failed to initialize thumbnail cache");
+
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -42,8 +191,81 @@ pub fn run() {
             secure_storage::secure_storage_store_batch,
             secure_storage::secure_storage_retrieve_batch,
             secure_storage::secure_storage_list_keys,
-            secure_storage::secure_storage_clear_all
+            secure_storage::secure_storage_clear_all,
+            thumb_cache::cache_purge,
+            http_client::network_stats,
+            proxy::proxy_get_settings,
+            proxy::proxy_set_settings,
+            proxy::proxy_test,
+            network_monitor::network_state,
+            dns::dns_get_settings,
+            dns::dns_set_settings,
+            dns::dns_diagnostics,
+            url_router::url_parse,
+            clipboard_watcher::clipboard_watcher_set_enabled,
+            clipboard_watcher::clipboard_watcher_enabled,
+            power::power_inhibit_acquire,
+            power::power_inhibit_release,
+            power::power_inhibit_list,
+            power::power_source_state,
+            native_messaging::native_messaging_install,
+            native_messaging::native_messaging_uninstall,
+            logging::logging_set_level,
+            logging::logging_get_levels,
+            logging::logs_tail,
+            telemetry::telemetry_get_settings,
+            telemetry::telemetry_set_settings,
+            crash_reports::crash_reports_list,
+            crash_reports::crash_reports_delete,
+            crash_reports::crash_reports_upload,
+            crash_reports::crash_reports_get_settings,
+            crash_reports::crash_reports_set_settings,
+            updates::update_check,
+            updates::update_install,
+            updates::update_get_channel,
+            updates::update_set_channel,
+            backup::backup_create,
+            backup::backup_restore,
+            privacy::privacy_export_all,
+            privacy::privacy_erase_request,
+            privacy::privacy_erase_all,
+            system_report::system_report,
+            autostart::autostart_set,
+            autostart::autostart_get,
+            shortcuts::shortcuts_get,
+            shortcuts::shortcuts_set,
+            shortcuts::shortcuts_check,
+            windows::zoom_set,
+            windows::zoom_get,
+            windows::window_set_always_on_top,
+            windows::window_compact_mode,
+            windows::window_titlebar_metrics,
+            windows::monitors_list,
+            windows::window_fullscreen_on_monitor,
+            windows::monitor_get_preferred,
+            windows::monitor_set_preferred,
+            gpu::gpu_acceleration_set,
+            gpu::gpu_diagnostics,
+            thumb_palette::thumb_palette,
+            theme::theme_get,
+            theme::theme_set,
+            i18n::get_locale,
+            i18n::set_locale,
+            first_run::first_run_state,
+            first_run::first_run_complete,
+            first_run::first_run_skip,
+            profiles::profiles_list,
+            profiles::profile_create,
+            profiles::profile_delete,
+            profiles::profile_switch
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(context)
+        .expect("error while building tauri application")
+        .run(|_app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                power::release_all();
+                telemetry::shutdown();
+                updates::install_staged();
+            }
+        });
 }
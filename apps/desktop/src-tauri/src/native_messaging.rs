@@ -0,0 +1,353 @@
+/// Native Messaging Module
+///
+/// Lets a companion browser extension hand the current tab's URL to the app.
+/// When a browser launches us as a native-messaging host we skip the GUI,
+/// read length-prefixed JSON messages from stdin, forward recognized links as
+/// `ytpub://open?url=...` (which reaches a running instance through the
+/// single-instance path, or launches the app) and reply on stdout.
+use serde::{Deserialize, Serialize};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Host name browsers use to find our manifest
+pub const HOST_NAME: &str = "pub.youtube.desktop";
+
+/// Largest message accepted from the extension
+const MAX_MESSAGE_BYTES: usize = 1024 * 1024;
+
+/// Browsers we can install a host manifest for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Browser {
+    Chrome,
+    Chromium,
+    Edge,
+    Firefox,
+}
+
+impl Browser {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "chrome" => Some(Browser::Chrome),
+            "chromium" => Some(Browser::Chromium),
+            "edge" => Some(Browser::Edge),
+            "firefox" => Some(Browser::Firefox),
+            _ => None,
+        }
+    }
+
+    /// Directory the browser reads host manifests from, relative to home
+    #[cfg(target_os = "linux")]
+    fn manifest_dir(self) -> &'static str {
+        match self {
+            Browser::Chrome => ".config/google-chrome/NativeMessagingHosts",
+            Browser::Chromium => ".config/chromium/NativeMessagingHosts",
+            Browser::Edge => ".config/microsoft-edge/NativeMessagingHosts",
+            Browser::Firefox => ".mozilla/native-messaging-hosts",
+        }
+    }
+
+    /// Directory the browser reads host manifests from, relative to home
+    #[cfg(target_os = "macos")]
+    fn manifest_dir(self) -> &'static str {
+        match self {
+            Browser::Chrome => "Library/Application Support/Google/Chrome/NativeMessagingHosts",
+            Browser::Chromium => "Library/Application Support/Chromium/NativeMessagingHosts",
+            Browser::Edge => "Library/Application Support/Microsoft Edge/NativeMessagingHosts",
+            Browser::Firefox => "Library/Application Support/Mozilla/NativeMessagingHosts",
+        }
+    }
+
+    /// Registry key pointing the browser at our manifest
+    #[cfg(target_os = "windows")]
+    fn registry_key(self) -> String {
+        let vendor = match self {
+            Browser::Chrome => "Google\\Chrome",
+            Browser::Chromium => "Chromium",
+            Browser::Edge => "Microsoft\\Edge",
+            Browser::Firefox => "Mozilla",
+        };
+        format!(
+            "HKCU\\Software\\{}\\NativeMessagingHosts\\{}",
+            vendor, HOST_NAME
+        )
+    }
+}
+
+/// Message sent by the extension
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum HostRequest {
+    Ping,
+    Open { url: String },
+}
+
+/// Reply written back to the extension
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct HostResponse {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    version: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Whether the process was launched by a browser as a native-messaging host
+///
+/// Chromium-based browsers pass the caller origin (`chrome-extension://id/`);
+/// Firefox passes the path of our manifest followed by the extension ID.
+pub fn is_host_launch(args: &[String]) -> bool {
+    args.iter().skip(1).any(|arg| {
+        arg.starts_with("chrome-extension://")
+            || PathBuf::from(arg)
+                .file_name()
+                .map(|name| name.to_string_lossy() == format!("{}.json", HOST_NAME))
+                .unwrap_or(false)
+    })
+}
+
+/// Serve native-messaging requests until the browser closes stdin
+pub fn run_host() {
+    let mut stdin = io::stdin().lock();
+    let mut stdout = io::stdout().lock();
+
+    while let Ok(Some(message)) = read_message(&mut stdin) {
+        let response = match serde_json::from_slice::<HostRequest>(&message) {
+            Ok(HostRequest::Ping) => HostResponse {
+                ok: true,
+                version: Some(env!("CARGO_PKG_VERSION")),
+                error: None,
+            },
+            Ok(HostRequest::Open { url }) => match open_in_app(&url) {
+                Ok(()) => HostResponse {
+                    ok: true,
+                    version: None,
+                    error: None,
+                },
+                Err(e) => HostResponse {
+                    ok: false,
+                    version: None,
+                    error: Some(e),
+                },
+            },
+            Err(e) => HostResponse {
+                ok: false,
+                version: None,
+                error: Some(format!("Invalid message: {}", e)),
+            },
+        };
+
+        if write_message(&mut stdout, &response).is_err() {
+            break;
+        }
+    }
+}
+
+/// Read one native-endian length-prefixed message
+///
+/// # Returns
+/// * `Ok(None)` when the browser closed the pipe
+fn read_message(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
+    let mut length = [0u8; 4];
+    match reader.read_exact(&mut length) {
+        Ok(()) => {}
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e),
+    }
+
+    let length = u32::from_ne_bytes(length) as usize;
+    if length > MAX_MESSAGE_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "Message too large",
+        ));
+    }
+
+    let mut message = vec![0u8; length];
+    reader.read_exact(&mut message)?;
+    Ok(Some(message))
+}
+
+fn write_message(writer: &mut impl Write, response: &HostResponse) -> io::Result<()> {
+    let body = serde_json::to_vec(response)?;
+    writer.write_all(&(body.len() as u32).to_ne_bytes())?;
+    writer.write_all(&body)?;
+    writer.flush()
+}
+
+/// Forward a link to the app as a `ytpub://open` deep link
+fn open_in_app(url: &str) -> Result<(), String> {
+    crate::security::validate_user_input(url, "URL", crate::security::MAX_URL_LENGTH)
+        .map_err(|e| format!("Invalid URL: {}", e))?;
+
+    if crate::url_router::parse(url).is_none() {
+        return Err("Not a supported YouTube link".to_string());
+    }
+
+    let link = format!(
+        "{}://open?url={}",
+        crate::url_router::APP_SCHEME,
+        percent_encoding::utf8_percent_encode(url, percent_encoding::NON_ALPHANUMERIC)
+    );
+
+    // macOS delivers deep links through Launch Services rather than argv
+    #[cfg(target_os = "macos")]
+    let spawned = std::process::Command::new("open").arg(&link).spawn();
+
+    // The single-instance plugin forwards argv to a running instance
+    #[cfg(not(target_os = "macos"))]
+    let spawned =
+        std::env::current_exe().and_then(|exe| std::process::Command::new(exe).arg(&link).spawn());
+
+    spawned
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open app: {}", e))
+}
+
+/// Write the host manifest for a browser
+///
+/// # Arguments
+/// * `app` - App handle used to resolve directories
+/// * `browser` - Browser to register with
+/// * `extension_id` - ID of the companion extension allowed to connect
+///
+/// # Returns
+/// * `Ok(PathBuf)` with the manifest path
+pub fn install<R: Runtime>(
+    app: &AppHandle<R>,
+    browser: Browser,
+    extension_id: &str,
+) -> Result<PathBuf, String> {
+    let exe = std::env::current_exe().map_err(|e| e.to_string())?;
+
+    let mut manifest = serde_json::json!({
+        "name": HOST_NAME,
+        "description": "youtube.pub",
+        "path": exe,
+        "type": "stdio",
+    });
+    if browser == Browser::Firefox {
+        manifest["allowed_extensions"] = serde_json::json!([extension_id]);
+    } else {
+        manifest["allowed_origins"] =
+            serde_json::json!([format!("chrome-extension://{}/", extension_id)]);
+    }
+
+    let path = manifest_path(app, browser)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let body = serde_json::to_vec_pretty(&manifest).map_err(|e| e.to_string())?;
+    std::fs::write(&path, body).map_err(|e| e.to_string())?;
+
+    #[cfg(target_os = "windows")]
+    {
+        let status = reg_command()
+            .args(["add", &browser.registry_key(), "/ve", "/t", "REG_SZ", "/d"])
+            .arg(&path)
+            .arg("/f")
+            .status()
+            .map_err(|e| e.to_string())?;
+        if !status.success() {
+            return Err("Failed to write registry key".to_string());
+        }
+    }
+
+    Ok(path)
+}
+
+/// Remove the host manifest for a browser
+pub fn uninstall<R: Runtime>(app: &AppHandle<R>, browser: Browser) -> Result<(), String> {
+    let path = manifest_path(app, browser)?;
+    if path.exists() {
+        std::fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let _ = reg_command()
+            .args(["delete", &browser.registry_key(), "/f"])
+            .status();
+    }
+
+    Ok(())
+}
+
+/// `reg.exe` without the console window it would otherwise flash, since
+/// release builds have none
+#[cfg(target_os = "windows")]
+fn reg_command() -> std::process::Command {
+    use std::os::windows::process::CommandExt;
+    const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+
+    let mut command = std::process::Command::new("reg");
+    command.creation_flags(CREATE_NO_WINDOW);
+    command
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn manifest_path<R: Runtime>(app: &AppHandle<R>, browser: Browser) -> Result<PathBuf, String> {
+    let home = app.path().home_dir().map_err(|e| e.to_string())?;
+    Ok(home
+        .join(browser.manifest_dir())
+        .join(format!("{}.json", HOST_NAME)))
+}
+
+/// On Windows the registry points at a manifest kept in our data dir
+#[cfg(target_os = "windows")]
+fn manifest_path<R: Runtime>(app: &AppHandle<R>, browser: Browser) -> Result<PathBuf, String> {
+    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let browser_dir = serde_json::to_value(browser)
+        .ok()
+        .and_then(|value| value.as_str().map(|s| s.to_string()))
+        .unwrap_or_default();
+    Ok(data_dir
+        .join("native-messaging")
+        .join(browser_dir)
+        .join(format!("{}.json", HOST_NAME)))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn manifest_path<R: Runtime>(_app: &AppHandle<R>, _browser: Browser) -> Result<PathBuf, String> {
+    Err("Native messaging is not supported on this platform".to_string())
+}
+
+/// Accept Chrome IDs (32 letters a-p) and Firefox IDs (`name@domain` or `{uuid}`)
+fn valid_extension_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= 128
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "@._-{}".contains(c))
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn native_messaging_install(
+    app_handle: tauri::AppHandle,
+    browser: String,
+    extension_id: String,
+) -> Result<String, String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(&extension_id, "extension ID", 128)
+        .map_err(|e| format!("Invalid extension ID: {}", e))?;
+    if !valid_extension_id(&extension_id) {
+        return Err("Invalid extension ID".to_string());
+    }
+    let browser = Browser::parse(&browser).ok_or("Unknown browser")?;
+
+    let path = install(&app_handle, browser, &extension_id)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+#[tauri::command]
+pub async fn native_messaging_uninstall(
+    app_handle: tauri::AppHandle,
+    browser: String,
+) -> Result<(), String> {
+    let browser = Browser::parse(&browser).ok_or("Unknown browser")?;
+    uninstall(&app_handle, browser)
+}
@@ -0,0 +1,162 @@
+/// Network Monitor Module
+///
+/// Periodically probes connectivity and, where the OS exposes it, whether the
+/// active connection is metered. State changes are emitted to the frontend as
+/// `network-changed` events and flip the shared HTTP client into offline mode,
+/// where requests fail fast instead of waiting on the network.
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
+
+/// Event emitted whenever the network state changes
+pub const NETWORK_CHANGED_EVENT: &str = "network-changed";
+
+/// Interval between connectivity probes
+const PROBE_INTERVAL_SECS: u64 = 15;
+
+/// Timeout for a single probe request
+const PROBE_TIMEOUT_SECS: u64 = 5;
+
+/// URLs probed for connectivity; any HTTP response means online
+const PROBE_URLS: &[&str] = &[
+    "https://www.youtube.com/generate_204",
+    "https://cloudflare.com/cdn-cgi/trace",
+];
+
+/// Current connectivity as seen by the backend
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkState {
+    pub online: bool,
+    /// Whether the connection is metered, if the OS reports it
+    pub metered: Option<bool>,
+    /// Unix time in seconds of the last change
+    pub changed_at: u64,
+}
+
+/// Last observed network state
+static NETWORK_STATE: Mutex<Option<NetworkState>> = Mutex::new(None);
+
+/// Get the last observed network state
+///
+/// # Returns
+/// * `Some(NetworkState)` once the first probe has completed
+/// * `None` before that
+pub fn current_state() -> Option<NetworkState> {
+    NETWORK_STATE.lock().unwrap().clone()
+}
+
+/// Start the background monitor
+///
+/// # Arguments
+/// * `app_handle` - Handle used to emit `network-changed` events
+pub fn start(app_handle: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let online = probe_connectivity().await;
+            let metered = detect_metered();
+            update_state(&app_handle, online, metered);
+
+            tokio::time::sleep(Duration::from_secs(PROBE_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Record a probe result, emitting an event and toggling offline mode on change
+fn update_state(app_handle: &tauri::AppHandle, online: bool, metered: Option<bool>) {
+    let mut state = NETWORK_STATE.lock().unwrap();
+
+    let changed = match state.as_ref() {
+        Some(previous) => previous.online != online || previous.metered != metered,
+        None => true,
+    };
+    if !changed {
+        return;
+    }
+
+    let next = NetworkState {
+        online,
+        metered,
+        changed_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0),
+    };
+    *state = Some(next.clone());
+    drop(state);
+
+    if let Some(client) = crate::http_client::get_http_client() {
+        client.set_offline(!online);
+    }
+
+    let _ = app_handle.emit(NETWORK_CHANGED_EVENT, next);
+}
+
+/// Send a HEAD request to any probe URL
+///
+/// Goes through the shared client so the configured proxy and DNS resolver are
+/// used, but not through `send`: the probe has to reach the network while the
+/// client is in offline mode, and must not feed the circuit breakers.
+async fn probe_connectivity() -> bool {
+    let Some(http_client) = crate::http_client::get_http_client() else {
+        return false;
+    };
+    let client = http_client.client_for("network_monitor");
+
+    for url in PROBE_URLS {
+        let attempt = client
+            .head(*url)
+            .timeout(Duration::from_secs(PROBE_TIMEOUT_SECS))
+            .send()
+            .await;
+
+        if attempt.is_ok() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Ask the OS whether the active connection is metered
+///
+/// Only NetworkManager on Linux is queried for now; other platforms report
+/// `None`.
+fn detect_metered() -> Option<bool> {
+    #[cfg(target_os = "linux")]
+    {
+        // NMMetered: 0 unknown, 1 yes, 2 no, 3 guess-yes, 4 guess-no
+        let output = std::process::Command::new("busctl")
+            .args([
+                "get-property",
+                "org.freedesktop.NetworkManager",
+                "/org/freedesktop/NetworkManager",
+                "org.freedesktop.NetworkManager",
+                "Metered",
+            ])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let stdout = String::from_utf8(output.stdout).ok()?;
+        match stdout.trim().strip_prefix("u ")?.trim() {
+            "1" | "3" => Some(true),
+            "2" | "4" => Some(false),
+            _ => None,
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn network_state(_app_handle: tauri::AppHandle) -> Result<Option<NetworkState>, String> {
+    Ok(current_state())
+}
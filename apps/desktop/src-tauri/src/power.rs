@@ -0,0 +1,498 @@
+/// Power Management Module
+///
+/// Reference-counted sleep inhibitors. Callers acquire a lease while long work
+/// is running and release it when done; the strongest outstanding lease decides
+/// what the OS is kept from doing. Platform mechanisms:
+/// - Windows: `SetThreadExecutionState` on a dedicated thread
+/// - macOS: a `caffeinate` child bound to our PID
+/// - Linux: a `systemd-inhibit` child bound to our PID
+///
+/// Also tracks battery level and AC/battery power source, emitting
+/// `power-state-changed` when the source changes.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Event emitted when the power source changes
+pub const POWER_STATE_CHANGED_EVENT: &str = "power-state-changed";
+
+/// Interval between power source checks
+const POWER_POLL_INTERVAL_SECS: u64 = 30;
+
+/// What an inhibitor keeps awake
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InhibitKind {
+    /// Prevent idle system sleep; the display may still turn off
+    System,
+    /// Keep the display on (implies `System`)
+    Display,
+}
+
+/// An outstanding inhibitor lease
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InhibitLease {
+    pub id: u64,
+    pub kind: InhibitKind,
+    pub reason: String,
+}
+
+/// Inhibitor manager state
+struct PowerState {
+    next_id: u64,
+    leases: HashMap<u64, InhibitLease>,
+    /// Kind currently applied to the OS, if any
+    applied: Option<InhibitKind>,
+    platform: platform::Inhibitor,
+}
+
+static POWER_STATE: once_cell::sync::Lazy<Mutex<PowerState>> = once_cell::sync::Lazy::new(|| {
+    Mutex::new(PowerState {
+        next_id: 1,
+        leases: HashMap::new(),
+        applied: None,
+        platform: platform::Inhibitor::new(),
+    })
+});
+
+/// Acquire an inhibitor lease
+///
+/// # Arguments
+/// * `kind` - What to keep awake
+/// * `reason` - Human-readable reason shown by OS tools where supported
+///
+/// # Returns
+/// * `Ok(u64)` with the lease ID to pass to `release`
+/// * `Err(String)` if the OS inhibitor could not be taken
+pub fn acquire(kind: InhibitKind, reason: &str) -> Result<u64, String> {
+    let mut state = POWER_STATE.lock().unwrap();
+    let id = state.next_id;
+    state.next_id += 1;
+    state.leases.insert(
+        id,
+        InhibitLease {
+            id,
+            kind,
+            reason: reason.to_string(),
+        },
+    );
+
+    if let Err(e) = reconcile(&mut state) {
+        state.leases.remove(&id);
+        return Err(e);
+    }
+    Ok(id)
+}
+
+/// Release an inhibitor lease
+///
+/// # Returns
+/// * `true` if the lease existed
+pub fn release(id: u64) -> bool {
+    let mut state = POWER_STATE.lock().unwrap();
+    let existed = state.leases.remove(&id).is_some();
+    let _ = reconcile(&mut state);
+    existed
+}
+
+/// Release every lease, e.g. on exit
+pub fn release_all() {
+    let mut state = POWER_STATE.lock().unwrap();
+    state.leases.clear();
+    let _ = reconcile(&mut state);
+}
+
+/// List outstanding leases
+pub fn leases() -> Vec<InhibitLease> {
+    let state = POWER_STATE.lock().unwrap();
+    let mut leases: Vec<InhibitLease> = state.leases.values().cloned().collect();
+    leases.sort_by_key(|lease| lease.id);
+    leases
+}
+
+/// RAII lease for backend code: released when dropped
+pub struct InhibitGuard {
+    id: u64,
+}
+
+impl InhibitGuard {
+    pub fn new(kind: InhibitKind, reason: &str) -> Result<Self, String> {
+        Ok(Self {
+            id: acquire(kind, reason)?,
+        })
+    }
+}
+
+impl Drop for InhibitGuard {
+    fn drop(&mut self) {
+        release(self.id);
+    }
+}
+
+/// Battery and power source as reported by the OS
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerSourceState {
+    /// `Some(true)` on battery, `Some(false)` on AC, `None` if unknown
+    pub on_battery: Option<bool>,
+    /// Remaining charge in percent, if the machine has a battery
+    pub battery_percent: Option<u8>,
+    /// Whether the battery is charging, if known
+    pub charging: Option<bool>,
+}
+
+/// Last observed power source
+static POWER_SOURCE: Mutex<Option<PowerSourceState>> = Mutex::new(None);
+
+/// Last observed power source, reading it now if it hasn't been yet
+pub fn power_source() -> PowerSourceState {
+    let cached = POWER_SOURCE.lock().unwrap().clone();
+    cached.unwrap_or_else(platform::read_power_source)
+}
+
+/// Whether the machine is known to be running on battery
+///
+/// Background work should use this to scale itself down.
+pub fn is_on_battery() -> bool {
+    power_source().on_battery == Some(true)
+}
+
+/// Start polling the power source
+///
+/// # Arguments
+/// * `app` - Handle used to emit `power-state-changed` events
+pub fn start_monitor<R: Runtime>(app: AppHandle<R>) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let current = tauri::async_runtime::spawn_blocking(platform::read_power_source)
+                .await
+                .unwrap_or(PowerSourceState {
+                    on_battery: None,
+                    battery_percent: None,
+                    charging: None,
+                });
+
+            let source_changed = {
+                let mut last = POWER_SOURCE.lock().unwrap();
+                let changed = match last.as_ref() {
+                    Some(previous) => previous.on_battery != current.on_battery,
+                    None => false,
+                };
+                *last = Some(current.clone());
+                changed
+            };
+
+            if source_changed {
+                let _ = app.emit(POWER_STATE_CHANGED_EVENT, current);
+            }
+
+            tokio::time::sleep(Duration::from_secs(POWER_POLL_INTERVAL_SECS)).await;
+        }
+    });
+}
+
+/// Apply the strongest outstanding lease to the OS
+fn reconcile(state: &mut PowerState) -> Result<(), String> {
+    let desired = state.leases.values().map(|lease| lease.kind).max();
+    if desired == state.applied {
+        return Ok(());
+    }
+
+    let reason = state
+        .leases
+        .values()
+        .filter(|lease| Some(lease.kind) == desired)
+        .map(|lease| lease.reason.clone())
+        .next()
+        .unwrap_or_default();
+
+    state.platform.apply(desired, &reason)?;
+    state.applied = desired;
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+    use super::InhibitKind;
+    use super::PowerSourceState;
+    use std::sync::mpsc::{channel, Sender};
+    use windows_sys::Win32::System::Power::{
+        GetSystemPowerStatus, SetThreadExecutionState, ES_CONTINUOUS, ES_DISPLAY_REQUIRED,
+        ES_SYSTEM_REQUIRED, SYSTEM_POWER_STATUS,
+    };
+
+    /// Read AC line and battery status
+    pub fn read_power_source() -> PowerSourceState {
+        let mut status: SYSTEM_POWER_STATUS = unsafe { std::mem::zeroed() };
+        if unsafe { GetSystemPowerStatus(&mut status) } == 0 {
+            return PowerSourceState {
+                on_battery: None,
+                battery_percent: None,
+                charging: None,
+            };
+        }
+
+        // BatteryFlag 128 = no system battery, 255 = unknown
+        let has_battery = status.BatteryFlag != 128 && status.BatteryFlag != 255;
+        PowerSourceState {
+            on_battery: match status.ACLineStatus {
+                0 => Some(true),
+                1 => Some(false),
+                _ => None,
+            },
+            battery_percent: if has_battery && status.BatteryLifePercent <= 100 {
+                Some(status.BatteryLifePercent)
+            } else {
+                None
+            },
+            charging: if has_battery {
+                Some(status.BatteryFlag & 8 != 0)
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Execution state is per-thread, so a dedicated thread holds it
+    pub struct Inhibitor {
+        sender: Sender<Option<InhibitKind>>,
+    }
+
+    impl Inhibitor {
+        pub fn new() -> Self {
+            let (sender, receiver) = channel::<Option<InhibitKind>>();
+            std::thread::spawn(move || {
+                for kind in receiver {
+                    let flags = match kind {
+                        Some(InhibitKind::Display) => {
+                            ES_CONTINUOUS | ES_SYSTEM_REQUIRED | ES_DISPLAY_REQUIRED
+                        }
+                        Some(InhibitKind::System) => ES_CONTINUOUS | ES_SYSTEM_REQUIRED,
+                        None => ES_CONTINUOUS,
+                    };
+                    unsafe {
+                        SetThreadExecutionState(flags);
+                    }
+                }
+            });
+            Self { sender }
+        }
+
+        pub fn apply(&mut self, kind: Option<InhibitKind>, _reason: &str) -> Result<(), String> {
+            self.sender
+                .send(kind)
+                .map_err(|e| format!("Power thread stopped: {}", e))
+        }
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "linux"))]
+mod platform {
+    use super::{InhibitKind, PowerSourceState};
+    use std::process::{Child, Command, Stdio};
+
+    /// Read power source from `pmset -g batt`
+    #[cfg(target_os = "macos")]
+    pub fn read_power_source() -> PowerSourceState {
+        let mut state = PowerSourceState {
+            on_battery: None,
+            battery_percent: None,
+            charging: None,
+        };
+
+        let Ok(output) = Command::new("pmset").args(["-g", "batt"]).output() else {
+            return state;
+        };
+        let Ok(text) = String::from_utf8(output.stdout) else {
+            return state;
+        };
+
+        // Now drawing from 'AC Power'
+        //  -InternalBattery-0 (id=1234)	85%; charging; 1:02 remaining present: true
+        if text.contains("'Battery Power'") {
+            state.on_battery = Some(true);
+        } else if text.contains("'AC Power'") {
+            state.on_battery = Some(false);
+        }
+
+        if let Some(line) = text.lines().find(|line| line.contains("InternalBattery")) {
+            state.battery_percent = line
+                .split(|c: char| c.is_whitespace() || c == ';')
+                .find_map(|part| part.strip_suffix('%'))
+                .and_then(|percent| percent.parse().ok());
+            state.charging = Some(line.contains("; charging") || line.contains("; charged"));
+        }
+
+        state
+    }
+
+    /// Read power source from `/sys/class/power_supply`
+    #[cfg(target_os = "linux")]
+    pub fn read_power_source() -> PowerSourceState {
+        let mut state = PowerSourceState {
+            on_battery: None,
+            battery_percent: None,
+            charging: None,
+        };
+        let mut mains_online = None;
+
+        let Ok(entries) = std::fs::read_dir("/sys/class/power_supply") else {
+            return state;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let read = |name: &str| {
+                std::fs::read_to_string(path.join(name))
+                    .ok()
+                    .map(|value| value.trim().to_string())
+            };
+
+            match read("type").as_deref() {
+                Some("Mains") => {
+                    if let Some(online) = read("online") {
+                        mains_online = Some(mains_online.unwrap_or(false) || online == "1");
+                    }
+                }
+                Some("Battery") if read("scope").as_deref() != Some("Device") => {
+                    state.battery_percent = read("capacity").and_then(|c| c.parse().ok());
+                    // "Full" and "Not charging" don't say whether AC is connected
+                    state.charging = read("status").map(|s| s == "Charging");
+                }
+                _ => {}
+            }
+        }
+
+        // Only a mains supply reports whether AC is connected
+        state.on_battery = mains_online.map(|online| !online);
+
+        state
+    }
+
+    /// The inhibitor lives as long as a child process that exits with us
+    pub struct Inhibitor {
+        child: Option<Child>,
+    }
+
+    impl Inhibitor {
+        pub fn new() -> Self {
+            Self { child: None }
+        }
+
+        pub fn apply(&mut self, kind: Option<InhibitKind>, reason: &str) -> Result<(), String> {
+            if let Some(mut child) = self.child.take() {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+
+            let Some(kind) = kind else {
+                return Ok(());
+            };
+
+            let child = Self::spawn(kind, reason)
+                .map_err(|e| format!("Failed to start sleep inhibitor: {}", e))?;
+            self.child = Some(child);
+            Ok(())
+        }
+
+        #[cfg(target_os = "macos")]
+        fn spawn(kind: InhibitKind, _reason: &str) -> std::io::Result<Child> {
+            let pid = std::process::id().to_string();
+            let flag = match kind {
+                InhibitKind::Display => "-di",
+                InhibitKind::System => "-i",
+            };
+            Command::new("caffeinate")
+                .args([flag, "-w", &pid])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+        }
+
+        #[cfg(target_os = "linux")]
+        fn spawn(kind: InhibitKind, reason: &str) -> std::io::Result<Child> {
+            let pid = std::process::id().to_string();
+            // systemd-inhibit has no display-only lock; "idle" is what keeps
+            // the session from blanking and suspending on inactivity
+            let what = match kind {
+                InhibitKind::Display => "idle:sleep",
+                InhibitKind::System => "sleep",
+            };
+            Command::new("systemd-inhibit")
+                .args([
+                    &format!("--what={}", what),
+                    "--who=youtube.pub",
+                    &format!("--why={}", reason),
+                    "--mode=block",
+                    "tail",
+                    &format!("--pid={}", pid),
+                    "-f",
+                    "/dev/null",
+                ])
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn()
+        }
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "macos", target_os = "linux")))]
+mod platform {
+    use super::{InhibitKind, PowerSourceState};
+
+    pub fn read_power_source() -> PowerSourceState {
+        PowerSourceState {
+            on_battery: None,
+            battery_percent: None,
+            charging: None,
+        }
+    }
+
+    pub struct Inhibitor;
+
+    impl Inhibitor {
+        pub fn new() -> Self {
+            Self
+        }
+
+        pub fn apply(&mut self, _kind: Option<InhibitKind>, _reason: &str) -> Result<(), String> {
+            Ok(())
+        }
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn power_inhibit_acquire(
+    _app_handle: tauri::AppHandle,
+    kind: InhibitKind,
+    reason: String,
+) -> Result<u64, String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(&reason, "reason", 255)
+        .map_err(|e| format!("Invalid reason: {}", e))?;
+
+    acquire(kind, &reason)
+}
+
+#[tauri::command]
+pub async fn power_inhibit_release(_app_handle: tauri::AppHandle, id: u64) -> Result<bool, String> {
+    Ok(release(id))
+}
+
+#[tauri::command]
+pub async fn power_inhibit_list(
+    _app_handle: tauri::AppHandle,
+) -> Result<Vec<InhibitLease>, String> {
+    Ok(leases())
+}
+
+#[tauri::command]
+pub async fn power_source_state(_app_handle: tauri::AppHandle) -> Result<PowerSourceState, String> {
+    Ok(power_source())
+}
@@ -0,0 +1,481 @@
+/// Privacy Module
+///
+/// Full data export and erasure. `privacy_export_all` writes a zip with a
+/// machine-readable `data.json` (every database row, every store, secure
+/// storage key names, crash reports, the same for every inactive profile)
+/// plus the saved thumbnail projects and log files.
+/// `privacy_erase_all` wipes everything the app keeps, other profiles
+/// included; it requires a short-lived token from `privacy_erase_request` so
+/// a single stray call cannot destroy data.
+use base64::{engine::general_purpose, Engine as _};
+use rand::RngCore;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Manager, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::backup::{collect_files, DATABASE_NAME, STORE_FILES, THUMBNAILS_DIR};
+
+/// How long an erase confirmation token stays valid
+const ERASE_TOKEN_TTL_SECS: u64 = 60;
+
+/// Outstanding erase confirmation token
+static ERASE_TOKEN: Mutex<Option<(String, Instant)>> = Mutex::new(None);
+
+/// Everything the app holds, as written to `data.json`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacyExport {
+    pub app_version: String,
+    /// Table name to rows; blobs are base64-encoded
+    pub database: BTreeMap<String, Vec<serde_json::Map<String, serde_json::Value>>>,
+    /// Store file name to its entries
+    pub stores: BTreeMap<String, serde_json::Value>,
+    /// Names of secrets held in secure storage; values are not exported
+    pub secure_storage_keys: Vec<String>,
+    pub crash_reports: Vec<crate::crash_reports::CrashReport>,
+    pub thumbnail_files: Vec<String>,
+    pub log_files: Vec<String>,
+    /// Profile the data above belongs to
+    pub active_profile: String,
+    /// Data of the profiles that aren't active
+    pub other_profiles: Vec<ProfileExport>,
+}
+
+/// An inactive profile's data, as written to `data.json`
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfileExport {
+    pub name: String,
+    pub database: BTreeMap<String, Vec<serde_json::Map<String, serde_json::Value>>>,
+    pub stores: BTreeMap<String, serde_json::Value>,
+    pub secure_storage_keys: Vec<String>,
+    pub thumbnail_files: Vec<String>,
+}
+
+/// What `privacy_erase_all` removed
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EraseReport {
+    pub secure_storage_cleared: bool,
+    pub image_cache_files: u64,
+    pub stores_cleared: usize,
+    pub database_rows: usize,
+    pub thumbnail_files: usize,
+    pub crash_reports: usize,
+    pub log_files: usize,
+    /// Inactive profiles deleted
+    pub profiles_removed: usize,
+    /// Steps that failed; erasure continues past failures
+    pub errors: Vec<String>,
+}
+
+fn database_path<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    let config_dir = app.path().app_config_dir().map_err(|e| e.to_string())?;
+    Ok(config_dir.join(DATABASE_NAME))
+}
+
+/// User tables of the gallery database
+fn user_tables(connection: &rusqlite::Connection) -> rusqlite::Result<Vec<String>> {
+    let mut statement = connection.prepare(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' \
+         AND name NOT LIKE '_sqlx_%' ORDER BY name",
+    )?;
+    let tables = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<rusqlite::Result<Vec<String>>>()?;
+    Ok(tables)
+}
+
+fn dump_database(
+    path: &Path,
+) -> rusqlite::Result<BTreeMap<String, Vec<serde_json::Map<String, serde_json::Value>>>> {
+    use rusqlite::types::ValueRef;
+
+    let mut dump = BTreeMap::new();
+    if !path.exists() {
+        return Ok(dump);
+    }
+
+    let connection =
+        rusqlite::Connection::open_with_flags(path, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    for table in user_tables(&connection)? {
+        let mut statement = connection.prepare(&format!("SELECT * FROM \"{}\"", table))?;
+        let columns: Vec<String> = statement
+            .column_names()
+            .iter()
+            .map(|c| c.to_string())
+            .collect();
+
+        let rows = statement
+            .query_map([], |row| {
+                let mut object = serde_json::Map::new();
+                for (index, column) in columns.iter().enumerate() {
+                    let value = match row.get_ref(index)? {
+                        ValueRef::Null => serde_json::Value::Null,
+                        ValueRef::Integer(i) => i.into(),
+                        ValueRef::Real(f) => f.into(),
+                        ValueRef::Text(t) => String::from_utf8_lossy(t).into_owned().into(),
+                        ValueRef::Blob(b) => general_purpose::STANDARD.encode(b).into(),
+                    };
+                    object.insert(column.clone(), value);
+                }
+                Ok(object)
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        dump.insert(table, rows);
+    }
+
+    Ok(dump)
+}
+
+/// Names of the secrets in a secure storage directory
+fn secure_storage_keys(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut keys: Vec<String> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let file_name = entry.file_name();
+            file_name
+                .to_str()?
+                .strip_suffix(".enc")
+                .map(|key| key.to_string())
+        })
+        .collect();
+    keys.sort();
+    keys
+}
+
+/// Read an inactive profile's data from where it is parked
+///
+/// # Returns
+/// * The profile's `data.json` entry and the thumbnail files to archive
+fn export_profile(
+    name: &str,
+    dir: &Path,
+) -> Result<(ProfileExport, Vec<(String, PathBuf)>), String> {
+    let mut stores = BTreeMap::new();
+    for store_name in STORE_FILES {
+        let Ok(contents) = fs::read_to_string(dir.join(store_name)) else {
+            continue;
+        };
+        if let Ok(value) = serde_json::from_str(&contents) {
+            stores.insert(store_name.to_string(), value);
+        }
+    }
+
+    let thumbnails_dir = dir.join(THUMBNAILS_DIR);
+    let mut thumbnail_files = Vec::new();
+    collect_files(&thumbnails_dir, &mut thumbnail_files).map_err(|e| e.to_string())?;
+    let prefix = format!("profiles/{}/{}", name, THUMBNAILS_DIR);
+    let files: Vec<(String, PathBuf)> = thumbnail_files
+        .into_iter()
+        .filter_map(|file| {
+            let archive_name = archive_name(&thumbnails_dir, &file, &prefix)?;
+            Some((archive_name, file))
+        })
+        .collect();
+
+    let export = ProfileExport {
+        name: name.to_string(),
+        database: dump_database(&dir.join(DATABASE_NAME)).map_err(|e| e.to_string())?,
+        stores,
+        secure_storage_keys: secure_storage_keys(&dir.join(crate::profiles::SECURE_STORAGE_DIR)),
+        thumbnail_files: files.iter().map(|(name, _)| name.clone()).collect(),
+    };
+    Ok((export, files))
+}
+
+/// Path of `file` inside the archive: `prefix` followed by its path below `base`
+fn archive_name(base: &Path, file: &Path, prefix: &str) -> Option<String> {
+    let relative = file.strip_prefix(base).ok()?;
+    let mut parts = vec![prefix.to_string()];
+    parts.extend(
+        relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy().into_owned()),
+    );
+    Some(parts.join("/"))
+}
+
+/// Write the full export archive
+///
+/// # Arguments
+/// * `app` - App handle used to resolve data directories and stores
+/// * `path` - Destination `.zip` path
+pub fn export_all<R: Runtime>(app: &AppHandle<R>, path: &Path) -> Result<(), String> {
+    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+    let log_dir = crate::logging::log_dir();
+
+    let mut stores = BTreeMap::new();
+    for name in STORE_FILES {
+        if let Ok(store) = app.store(*name) {
+            let entries: serde_json::Map<String, serde_json::Value> =
+                store.entries().into_iter().collect();
+            stores.insert(name.to_string(), serde_json::Value::Object(entries));
+        }
+    }
+
+    let mut thumbnail_files = Vec::new();
+    collect_files(&data_dir.join(THUMBNAILS_DIR), &mut thumbnail_files)
+        .map_err(|e| e.to_string())?;
+    let mut log_files = Vec::new();
+    if let Some(log_dir) = &log_dir {
+        collect_files(log_dir, &mut log_files).map_err(|e| e.to_string())?;
+    }
+
+    let mut files: Vec<(String, PathBuf)> = Vec::new();
+    for file in &thumbnail_files {
+        if let Some(name) = archive_name(&data_dir.join(THUMBNAILS_DIR), file, THUMBNAILS_DIR) {
+            files.push((name, file.clone()));
+        }
+    }
+    if let Some(log_dir) = &log_dir {
+        for file in &log_files {
+            if let Some(name) = archive_name(log_dir, file, "logs") {
+                files.push((name, file.clone()));
+            }
+        }
+    }
+
+    let profiles = crate::profiles::state(app)?;
+    let mut other_profiles = Vec::new();
+    let mut profile_files = Vec::new();
+    for name in profiles
+        .profiles
+        .iter()
+        .filter(|name| **name != profiles.active)
+    {
+        let dir = crate::profiles::parked_dir(app, name)?;
+        let (export, parked_files) = export_profile(name, &dir)?;
+        other_profiles.push(export);
+        profile_files.extend(parked_files);
+    }
+
+    let export = PrivacyExport {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        database: dump_database(&database_path(app)?).map_err(|e| e.to_string())?,
+        stores,
+        secure_storage_keys: crate::secure_storage::get_secure_storage()
+            .and_then(|storage| storage.list_keys().ok())
+            .unwrap_or_default(),
+        crash_reports: crate::crash_reports::list(),
+        thumbnail_files: files
+            .iter()
+            .filter(|(name, _)| name.starts_with(THUMBNAILS_DIR))
+            .map(|(name, _)| name.clone())
+            .collect(),
+        log_files: files
+            .iter()
+            .filter(|(name, _)| name.starts_with("logs/"))
+            .map(|(name, _)| name.clone())
+            .collect(),
+        active_profile: profiles.active,
+        other_profiles,
+    };
+    files.extend(profile_files);
+
+    let temp_path = path.with_extension("zip.partial");
+    let result = (|| -> Result<(), String> {
+        let file = fs::File::create(&temp_path).map_err(|e| e.to_string())?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        zip.start_file("data.json", options)
+            .map_err(|e| e.to_string())?;
+        let body = serde_json::to_vec_pretty(&export).map_err(|e| e.to_string())?;
+        zip.write_all(&body).map_err(|e| e.to_string())?;
+
+        for (name, source) in &files {
+            zip.start_file(name.as_str(), options)
+                .map_err(|e| e.to_string())?;
+            let mut input = fs::File::open(source).map_err(|e| e.to_string())?;
+            std::io::copy(&mut input, &mut zip).map_err(|e| e.to_string())?;
+        }
+
+        zip.finish().map_err(|e| e.to_string())?;
+        Ok(())
+    })();
+
+    match result {
+        Ok(()) => fs::rename(&temp_path, path).map_err(|e| e.to_string()),
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path);
+            Err(e)
+        }
+    }
+}
+
+/// Issue a token that must be passed to `erase_all` within a minute
+pub fn erase_request() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let token = hex::encode(bytes);
+    *ERASE_TOKEN.lock().unwrap() = Some((token.clone(), Instant::now()));
+    token
+}
+
+/// Consume the outstanding token if it matches and hasn't expired
+fn take_token(token: &str) -> bool {
+    let mut outstanding = ERASE_TOKEN.lock().unwrap();
+    match outstanding.take() {
+        Some((expected, issued)) => {
+            expected == token && issued.elapsed() < Duration::from_secs(ERASE_TOKEN_TTL_SECS)
+        }
+        None => false,
+    }
+}
+
+/// Erase all user data
+///
+/// Background activity is stopped first so nothing is rewritten mid-erase,
+/// then secrets, caches, stores, database rows, thumbnails, other profiles,
+/// crash reports and finally logs (last, so failures above are still logged).
+/// The frontend should relaunch afterwards.
+pub fn erase_all<R: Runtime>(app: &AppHandle<R>) -> Result<EraseReport, String> {
+    let mut report = EraseReport::default();
+    let data_dir = app.path().app_data_dir().map_err(|e| e.to_string())?;
+
+    // 1. Stop background readers/writers
+    crate::clipboard_watcher::set_enabled(app, false);
+    crate::power::release_all();
+
+    // 2. Secrets
+    match crate::secure_storage::get_secure_storage() {
+        Some(storage) => match storage.clear_all() {
+            Ok(()) => report.secure_storage_cleared = true,
+            Err(e) => report.errors.push(format!("Secure storage: {}", e)),
+        },
+        None => report
+            .errors
+            .push("Secure storage not initialized".to_string()),
+    }
+
+    // 3. Caches
+    if let Some(cache) = crate::thumb_cache::get_thumb_cache() {
+        for kind in crate::thumb_cache::CacheKind::ALL {
+            match cache.purge(kind) {
+                Ok(result) => report.image_cache_files += result.files_removed,
+                Err(e) => report.errors.push(format!("Image cache: {}", e)),
+            }
+        }
+    }
+
+    // 4. Stores
+    for name in STORE_FILES {
+        match app.store(*name) {
+            Ok(store) => {
+                store.clear();
+                match store.save() {
+                    Ok(()) => report.stores_cleared += 1,
+                    Err(e) => report.errors.push(format!("{}: {}", name, e)),
+                }
+            }
+            Err(e) => report.errors.push(format!("{}: {}", name, e)),
+        }
+    }
+
+    // 5. Database rows; the file stays because the frontend holds it open
+    match erase_database(&database_path(app)?) {
+        Ok(rows) => report.database_rows = rows,
+        Err(e) => report.errors.push(format!("Database: {}", e)),
+    }
+
+    // 6. Saved thumbnails
+    let mut thumbnail_files = Vec::new();
+    let thumbnails_dir = data_dir.join(THUMBNAILS_DIR);
+    let _ = collect_files(&thumbnails_dir, &mut thumbnail_files);
+    if thumbnails_dir.exists() {
+        match fs::remove_dir_all(&thumbnails_dir) {
+            Ok(()) => report.thumbnail_files = thumbnail_files.len(),
+            Err(e) => report.errors.push(format!("Thumbnails: {}", e)),
+        }
+    }
+
+    // 7. Other profiles and the profile list
+    match crate::profiles::erase_inactive(app) {
+        Ok(count) => report.profiles_removed = count,
+        Err(e) => report.errors.push(format!("Profiles: {}", e)),
+    }
+
+    // 8. Crash reports
+    for crash_report in crate::crash_reports::list() {
+        if crate::crash_reports::delete(&crash_report.id) {
+            report.crash_reports += 1;
+        }
+    }
+
+    // 9. Logs; the file currently being written may be locked on Windows
+    for error in &report.errors {
+        tracing::warn!("Privacy erase step failed: {}", error);
+    }
+    if let Some(log_dir) = crate::logging::log_dir() {
+        let mut log_files = Vec::new();
+        let _ = collect_files(&log_dir, &mut log_files);
+        for file in log_files {
+            if fs::remove_file(&file).is_ok() {
+                report.log_files += 1;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+fn erase_database(path: &Path) -> rusqlite::Result<usize> {
+    if !path.exists() {
+        return Ok(0);
+    }
+
+    let connection = rusqlite::Connection::open(path)?;
+    let mut rows = 0;
+    for table in user_tables(&connection)? {
+        rows += connection.execute(&format!("DELETE FROM \"{}\"", table), [])?;
+    }
+    // Rewrite the file so deleted rows don't linger in free pages
+    connection.execute_batch("VACUUM;")?;
+    Ok(rows)
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn privacy_export_all(app_handle: tauri::AppHandle, path: String) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(&path, "path", 4096)
+        .map_err(|e| format!("Invalid path: {}", e))?;
+
+    tauri::async_runtime::spawn_blocking(move || export_all(&app_handle, Path::new(&path)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn privacy_erase_request(_app_handle: tauri::AppHandle) -> Result<String, String> {
+    Ok(erase_request())
+}
+
+#[tauri::command]
+pub async fn privacy_erase_all(
+    app_handle: tauri::AppHandle,
+    token: String,
+) -> Result<EraseReport, String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(&token, "token", 64)
+        .map_err(|e| format!("Invalid token: {}", e))?;
+    if !take_token(&token) {
+        return Err(crate::i18n::tr("privacy-invalid-token"));
+    }
+
+    tauri::async_runtime::spawn_blocking(move || erase_all(&app_handle))
+        .await
+        .map_err(|e| e.to_string())?
+}
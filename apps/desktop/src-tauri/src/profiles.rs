@@ -0,0 +1,485 @@
+/// Profiles Module
+///
+/// Named profiles, each with its own gallery database, stores, saved
+/// thumbnails, secure storage and webview storage (cookies, local storage). The
+/// active profile's data stays in the usual locations so the frontend and the
+/// other modules are unaware of profiles; the others are parked in
+/// `profiles/<name>/` in the app data directory. Webview storage isn't moved:
+/// the main window is created with the active profile's own data directory
+/// (a data store on macOS).
+///
+/// Switching is done at launch, before anything opens the data: `profile_switch`
+/// records the choice and restarts, and `apply_pending_switch` swaps the data
+/// in. `--profile <name>` picks (or creates) a profile without going through
+/// the frontend, whether it starts the app or is forwarded to a running one.
+use serde::{Deserialize, Serialize};
+#[cfg(target_os = "macos")]
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::backup::{DATABASE_NAME, STORE_FILES, THUMBNAILS_DIR};
+use crate::i18n::{tr, tr_args};
+
+/// Command-line flag selecting a profile
+pub const PROFILE_ARG: &str = "--profile";
+
+/// Profile list, in the app data directory; not part of any profile
+const PROFILES_FILE: &str = "profiles.json";
+/// Switch in progress, in the app data directory; not part of any profile
+const SWITCH_JOURNAL_FILE: &str = "profile-switch.json";
+/// Parked profiles, in the app data directory
+const PROFILES_DIR: &str = "profiles";
+/// Per-profile webview storage, in the app data directory; never parked
+const WEBVIEW_DIR: &str = "webview";
+/// Secure storage directory in the app data directory
+pub const SECURE_STORAGE_DIR: &str = "secure_storage";
+/// Profile that existing installs' data belongs to
+const DEFAULT_PROFILE: &str = "default";
+
+/// Limits on profile names; they become directory names
+const MAX_PROFILE_NAME_LENGTH: usize = 32;
+const MAX_PROFILES: usize = 16;
+
+/// Stored profile list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProfilesState {
+    /// Profile whose data is in place
+    pub active: String,
+    pub profiles: Vec<String>,
+    /// Profile to switch to at the next launch
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending: Option<String>,
+}
+
+impl Default for ProfilesState {
+    fn default() -> Self {
+        Self {
+            active: DEFAULT_PROFILE.to_string(),
+            profiles: vec![DEFAULT_PROFILE.to_string()],
+            pending: None,
+        }
+    }
+}
+
+/// Record of a switch in progress
+///
+/// Written before any data moves and removed once the switch is complete, so a
+/// switch cut short by a crash or an error is resumed at the next launch
+/// instead of being started over on top of half-moved data.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SwitchJournal {
+    from: String,
+    to: String,
+    /// Whether all of `from`'s data has been parked
+    parked: bool,
+}
+
+fn valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_PROFILE_NAME_LENGTH
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    crate::security::validate_user_input(name, "profile", MAX_PROFILE_NAME_LENGTH)
+        .map_err(|e| format!("Invalid profile name: {}", e))?;
+    if !valid_name(name) {
+        return Err(tr_args(
+            "profile-invalid-name",
+            &[("max", MAX_PROFILE_NAME_LENGTH.into())],
+        ));
+    }
+    Ok(())
+}
+
+/// Profile requested in a command line, if any
+fn requested_profile(args: impl IntoIterator<Item = String>) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == PROFILE_ARG {
+            return args.next();
+        }
+        if let Some(name) = arg
+            .strip_prefix(PROFILE_ARG)
+            .and_then(|s| s.strip_prefix('='))
+        {
+            return Some(name.to_string());
+        }
+    }
+    None
+}
+
+fn state_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(PROFILES_FILE)
+}
+
+fn load_state(data_dir: &Path) -> ProfilesState {
+    fs::read_to_string(state_path(data_dir))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Write JSON next to its final name, then rename, so a crash never leaves a
+/// truncated file
+fn write_json<T: Serialize>(data_dir: &Path, file_name: &str, value: &T) -> io::Result<()> {
+    fs::create_dir_all(data_dir)?;
+    let contents = serde_json::to_string_pretty(value)?;
+    let temp_path = data_dir.join(format!("{}.tmp", file_name));
+    fs::write(&temp_path, contents)?;
+    fs::rename(&temp_path, data_dir.join(file_name))
+}
+
+fn save_state(data_dir: &Path, state: &ProfilesState) -> io::Result<()> {
+    write_json(data_dir, PROFILES_FILE, state)
+}
+
+fn load_journal(data_dir: &Path) -> Option<SwitchJournal> {
+    fs::read_to_string(data_dir.join(SWITCH_JOURNAL_FILE))
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+}
+
+fn save_journal(data_dir: &Path, journal: &SwitchJournal) -> io::Result<()> {
+    write_json(data_dir, SWITCH_JOURNAL_FILE, journal)
+}
+
+/// Move a file or directory to `to`, which must not exist yet
+///
+/// Nothing is ever overwritten: whatever is at `to` may be the only copy of a
+/// profile's data.
+fn move_entry(from: &Path, to: &Path) -> io::Result<()> {
+    if !from.exists() {
+        return Ok(());
+    }
+    if to.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        ));
+    }
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    if fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    // The config dir may be on another filesystem; only the database lives
+    // there, so a file copy is enough. Copy under a temporary name so `to`
+    // only ever appears complete.
+    let mut partial = to.as_os_str().to_owned();
+    partial.push(".partial");
+    let partial = PathBuf::from(partial);
+    fs::copy(from, &partial)?;
+    fs::rename(&partial, to)?;
+    fs::remove_file(from)
+}
+
+/// Locations of one profile's data, paired with where it is parked
+fn profile_entries(
+    data_dir: &Path,
+    config_dir: &Path,
+    parked_dir: &Path,
+) -> Vec<(PathBuf, PathBuf)> {
+    let mut entries: Vec<(PathBuf, PathBuf)> = STORE_FILES
+        .iter()
+        .chain([&THUMBNAILS_DIR, &SECURE_STORAGE_DIR])
+        .map(|name| (data_dir.join(name), parked_dir.join(name)))
+        .collect();
+    for suffix in ["", "-wal", "-shm"] {
+        let name = format!("{}{}", DATABASE_NAME, suffix);
+        entries.push((config_dir.join(&name), parked_dir.join(&name)));
+    }
+    entries
+}
+
+/// Switch to the profile requested on the command line or by `profile_switch`
+///
+/// Must run before anything opens the profile data, i.e. right after a pending
+/// backup restore is applied (the restore belongs to the profile that staged it).
+///
+/// # Returns
+/// * `Ok(String)` with the active profile
+/// * `Err(String)` if the switch failed or the requested profile was refused;
+///   the previous profile stays active
+pub fn apply_pending_switch<R: Runtime>(app: &AppHandle<R>) -> Result<String, String> {
+    let path = app.path();
+    let data_dir = path.app_data_dir().map_err(|e| e.to_string())?;
+    let config_dir = path.app_config_dir().map_err(|e| e.to_string())?;
+
+    let mut state = load_state(&data_dir);
+    if let Some(journal) = load_journal(&data_dir) {
+        // An earlier switch was cut short; finish it before anything else
+        return run_switch(&data_dir, &config_dir, &mut state, journal);
+    }
+
+    // A pending switch is newer than the command line, which a restart repeats
+    let Some(target) = state
+        .pending
+        .take()
+        .or_else(|| requested_profile(std::env::args()))
+    else {
+        return Ok(state.active);
+    };
+    if let Err(e) = validate_name(&target).and_then(|()| check_limit(&state, &target)) {
+        save_state(&data_dir, &state).map_err(|e| e.to_string())?;
+        return Err(format!("Ignoring requested profile: {}", e));
+    }
+    if target == state.active {
+        save_state(&data_dir, &state).map_err(|e| e.to_string())?;
+        return Ok(state.active);
+    }
+
+    // From here on the journal carries the request
+    let journal = SwitchJournal {
+        from: state.active.clone(),
+        to: target,
+        parked: false,
+    };
+    save_journal(&data_dir, &journal).map_err(|e| e.to_string())?;
+    save_state(&data_dir, &state).map_err(|e| e.to_string())?;
+    run_switch(&data_dir, &config_dir, &mut state, journal)
+}
+
+/// Carry out (or resume) a journaled switch
+///
+/// Parks `from`'s data, then moves `to`'s data (if any) in; a profile seen for
+/// the first time starts empty. Each step skips entries that were already
+/// moved, so it can be repeated after an interruption.
+fn run_switch(
+    data_dir: &Path,
+    config_dir: &Path,
+    state: &mut ProfilesState,
+    mut journal: SwitchJournal,
+) -> Result<String, String> {
+    let profiles_dir = data_dir.join(PROFILES_DIR);
+
+    // Once parking is done the live data may already be `to`'s, so it must
+    // never be parked again
+    if !journal.parked {
+        let from_dir = profiles_dir.join(&journal.from);
+        for (live, parked) in profile_entries(data_dir, config_dir, &from_dir) {
+            move_entry(&live, &parked).map_err(|e| e.to_string())?;
+        }
+        journal.parked = true;
+        save_journal(data_dir, &journal).map_err(|e| e.to_string())?;
+    }
+
+    let to_dir = profiles_dir.join(&journal.to);
+    for (live, parked) in profile_entries(data_dir, config_dir, &to_dir) {
+        move_entry(&parked, &live).map_err(|e| e.to_string())?;
+    }
+    if to_dir.exists() {
+        let _ = fs::remove_dir_all(&to_dir);
+    }
+
+    if !state.profiles.contains(&journal.to) {
+        state.profiles.push(journal.to.clone());
+    }
+    if state.pending.as_ref() == Some(&journal.to) {
+        state.pending = None;
+    }
+    state.active = journal.to.clone();
+    save_state(data_dir, state).map_err(|e| e.to_string())?;
+    fs::remove_file(data_dir.join(SWITCH_JOURNAL_FILE)).map_err(|e| e.to_string())?;
+    Ok(journal.to)
+}
+
+fn resolve_data_dir<R: Runtime>(app: &AppHandle<R>) -> Result<PathBuf, String> {
+    app.path().app_data_dir().map_err(|e| e.to_string())
+}
+
+/// Refuse to add a profile past `MAX_PROFILES`
+fn check_limit(state: &ProfilesState, name: &str) -> Result<(), String> {
+    if !state.profiles.iter().any(|profile| profile == name) && state.profiles.len() >= MAX_PROFILES
+    {
+        return Err(tr_args("profile-limit", &[("max", MAX_PROFILES.into())]));
+    }
+    Ok(())
+}
+
+/// Switch to the profile named in a command line forwarded by a second launch
+///
+/// Like at startup, an unknown profile is created. The switch itself happens
+/// in a fresh process, so the app restarts unless the profile is already
+/// active.
+///
+/// # Arguments
+/// * `app` - App handle
+/// * `args` - Command line of the second launch
+pub fn switch_from_args<R: Runtime>(app: &AppHandle<R>, args: &[String]) -> Result<(), String> {
+    let Some(name) = requested_profile(args.iter().cloned()) else {
+        return Ok(());
+    };
+    validate_name(&name)?;
+
+    let data_dir = resolve_data_dir(app)?;
+    let mut state = load_state(&data_dir);
+    if name == state.active {
+        return Ok(());
+    }
+    check_limit(&state, &name)?;
+
+    state.pending = Some(name);
+    save_state(&data_dir, &state).map_err(|e| e.to_string())?;
+    app.restart();
+}
+
+/// Directory the main window's webview keeps the active profile's cookies,
+/// local storage and cache in
+///
+/// `None` for the default profile, which keeps the platform's default location
+/// so existing installs don't lose their webview data. Used on Windows and
+/// Linux; macOS uses `webview_data_store_id` instead.
+#[cfg(not(target_os = "macos"))]
+pub fn webview_data_dir<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    let data_dir = resolve_data_dir(app).ok()?;
+    let active = load_state(&data_dir).active;
+    (active != DEFAULT_PROFILE).then(|| data_dir.join(WEBVIEW_DIR).join(active))
+}
+
+/// WebKit data store identifier for the active profile's webview storage
+///
+/// `None` for the default profile, which keeps the default data store.
+#[cfg(target_os = "macos")]
+pub fn webview_data_store_id<R: Runtime>(app: &AppHandle<R>) -> Option<[u8; 16]> {
+    let active = state(app).ok()?.active;
+    if active == DEFAULT_PROFILE {
+        return None;
+    }
+    let digest = Sha256::digest(format!("profile:{}", active).as_bytes());
+    let mut id = [0u8; 16];
+    id.copy_from_slice(&digest[..16]);
+    Some(id)
+}
+
+/// Stored profile list
+pub fn state<R: Runtime>(app: &AppHandle<R>) -> Result<ProfilesState, String> {
+    Ok(load_state(&resolve_data_dir(app)?))
+}
+
+/// Directory a profile's data is parked in while it isn't active
+///
+/// Laid out like the live data: stores, `thumbnails/`, `secure_storage/` and
+/// the gallery database side by side.
+pub fn parked_dir<R: Runtime>(app: &AppHandle<R>, name: &str) -> Result<PathBuf, String> {
+    Ok(resolve_data_dir(app)?.join(PROFILES_DIR).join(name))
+}
+
+/// Delete every parked profile and the profile list
+///
+/// The live data, now belonging to no named profile, becomes the default
+/// profile again at the next launch.
+///
+/// # Returns
+/// * Number of parked profiles removed
+pub fn erase_inactive<R: Runtime>(app: &AppHandle<R>) -> Result<usize, String> {
+    let data_dir = resolve_data_dir(app)?;
+    let state = load_state(&data_dir);
+
+    let profiles_dir = data_dir.join(PROFILES_DIR);
+    let removed = state
+        .profiles
+        .iter()
+        .filter(|name| **name != state.active && profiles_dir.join(name.as_str()).exists())
+        .count();
+    if profiles_dir.exists() {
+        fs::remove_dir_all(&profiles_dir).map_err(|e| e.to_string())?;
+    }
+    // The active profile's webview storage is in use; it goes with the next
+    // erase once the app is back on the default profile
+    for name in state.profiles.iter().filter(|name| **name != state.active) {
+        let _ = fs::remove_dir_all(data_dir.join(WEBVIEW_DIR).join(name));
+    }
+    let path = state_path(&data_dir);
+    if path.exists() {
+        fs::remove_file(&path).map_err(|e| e.to_string())?;
+    }
+    Ok(removed)
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn profiles_list(app_handle: tauri::AppHandle) -> Result<ProfilesState, String> {
+    state(&app_handle)
+}
+
+#[tauri::command]
+pub async fn profile_create(app_handle: tauri::AppHandle, name: String) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    validate_name(&name)?;
+
+    let data_dir = resolve_data_dir(&app_handle)?;
+    let mut state = load_state(&data_dir);
+    if state.profiles.contains(&name) {
+        return Err(tr_args("profile-exists", &[("name", name.as_str().into())]));
+    }
+    check_limit(&state, &name)?;
+
+    state.profiles.push(name);
+    save_state(&data_dir, &state).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn profile_delete(app_handle: tauri::AppHandle, name: String) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    validate_name(&name)?;
+
+    let data_dir = resolve_data_dir(&app_handle)?;
+    let mut state = load_state(&data_dir);
+    if name == state.active {
+        return Err(tr("profile-delete-active"));
+    }
+    if !state.profiles.contains(&name) {
+        return Err(tr_args(
+            "profile-unknown",
+            &[("name", name.as_str().into())],
+        ));
+    }
+
+    for dir in [
+        data_dir.join(PROFILES_DIR).join(&name),
+        data_dir.join(WEBVIEW_DIR).join(&name),
+    ] {
+        if dir.exists() {
+            fs::remove_dir_all(&dir).map_err(|e| e.to_string())?;
+        }
+    }
+    state.profiles.retain(|profile| profile != &name);
+    if state.pending.as_ref() == Some(&name) {
+        state.pending = None;
+    }
+    save_state(&data_dir, &state).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn profile_switch(app_handle: tauri::AppHandle, name: String) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    validate_name(&name)?;
+
+    let data_dir = resolve_data_dir(&app_handle)?;
+    let mut state = load_state(&data_dir);
+    if !state.profiles.contains(&name) {
+        return Err(tr_args(
+            "profile-unknown",
+            &[("name", name.as_str().into())],
+        ));
+    }
+    if name == state.active {
+        return Ok(());
+    }
+
+    state.pending = Some(name);
+    save_state(&data_dir, &state).map_err(|e| e.to_string())?;
+
+    // The database and stores are open; swap them in from a fresh process
+    app_handle.restart();
+}
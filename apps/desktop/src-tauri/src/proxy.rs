@@ -0,0 +1,322 @@
+/// Proxy Configuration Module
+///
+/// Stores the user's proxy settings and turns them into client configuration
+/// for the shared HTTP client. Proxy credentials are kept in secure storage;
+/// everything else lives in the settings store.
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::i18n::{tr, tr_args};
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding the serialized `ProxySettings`
+const PROXY_SETTINGS_FIELD: &str = "proxy_settings";
+/// Secure storage key for the proxy password
+const PROXY_PASSWORD_KEY: &str = "proxy_password";
+/// Endpoint used by `proxy_test` to discover the exit IP
+const EXIT_IP_ENDPOINT: &str = "https://api.ipify.org?format=json";
+/// Limit on bypass hosts and bypass modules, each
+const MAX_BYPASS_RULES: usize = 100;
+
+/// How outbound traffic is routed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyMode {
+    /// Always connect directly
+    None,
+    /// Use the proxy detected from the system environment
+    System,
+    /// Use the manually configured proxy
+    Manual,
+}
+
+/// Protocol spoken by a manual proxy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProxyKind {
+    Http,
+    Socks5,
+}
+
+/// User-facing proxy configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxySettings {
+    pub mode: ProxyMode,
+    pub kind: ProxyKind,
+    pub host: String,
+    pub port: u16,
+    /// Username for proxy authentication; the password is in secure storage
+    pub username: Option<String>,
+    /// Hosts (or `.domain` suffixes) that always connect directly
+    pub bypass_hosts: Vec<String>,
+    /// Backend modules (e.g. `thumb_cache`) that always connect directly
+    pub bypass_modules: Vec<String>,
+}
+
+impl Default for ProxySettings {
+    fn default() -> Self {
+        Self {
+            mode: ProxyMode::System,
+            kind: ProxyKind::Http,
+            host: String::new(),
+            port: 8080,
+            username: None,
+            bypass_hosts: Vec::new(),
+            bypass_modules: Vec::new(),
+        }
+    }
+}
+
+/// Result of a `proxy_test` run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyTestResult {
+    pub ok: bool,
+    pub exit_ip: Option<String>,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+}
+
+/// Apply proxy settings to a client builder
+///
+/// # Arguments
+/// * `builder` - Builder to configure
+/// * `settings` - Proxy settings
+/// * `password` - Proxy password, if one is stored
+///
+/// # Returns
+/// * `Ok(ClientBuilder)` configured for the settings
+/// * `Err(String)` if the manual proxy URL is invalid
+pub fn configure_client(
+    builder: reqwest::ClientBuilder,
+    settings: &ProxySettings,
+    password: Option<&str>,
+) -> Result<reqwest::ClientBuilder, String> {
+    match settings.mode {
+        ProxyMode::None => Ok(builder.no_proxy()),
+        // reqwest picks up HTTP(S)_PROXY / ALL_PROXY / NO_PROXY by default
+        ProxyMode::System => Ok(builder),
+        ProxyMode::Manual => {
+            if settings.host.trim().is_empty() {
+                return Err(tr("proxy-host-required"));
+            }
+
+            let scheme = match settings.kind {
+                ProxyKind::Http => "http",
+                ProxyKind::Socks5 => "socks5h",
+            };
+            let proxy_url = format!("{}://{}:{}", scheme, settings.host.trim(), settings.port);
+
+            let mut proxy = reqwest::Proxy::all(&proxy_url)
+                .map_err(|e| tr_args("proxy-invalid", &[("error", e.to_string().into())]))?;
+            if let Some(username) = settings.username.as_deref().filter(|u| !u.is_empty()) {
+                proxy = proxy.basic_auth(username, password.unwrap_or_default());
+            }
+            if !settings.bypass_hosts.is_empty() {
+                proxy = proxy.no_proxy(reqwest::NoProxy::from_string(
+                    &settings.bypass_hosts.join(","),
+                ));
+            }
+
+            Ok(builder.proxy(proxy))
+        }
+    }
+}
+
+/// Load the stored proxy settings, falling back to defaults
+pub fn load_settings<R: Runtime>(app_handle: &AppHandle<R>) -> ProxySettings {
+    app_handle
+        .store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(PROXY_SETTINGS_FIELD))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Load the stored proxy password from secure storage
+pub fn load_password() -> Option<String> {
+    crate::secure_storage::get_secure_storage()
+        .and_then(|storage| storage.retrieve(PROXY_PASSWORD_KEY).ok().flatten())
+}
+
+/// Route a client built outside the shared HTTP client (telemetry export,
+/// DoH queries) through the stored proxy settings
+///
+/// Secure storage must be initialized for a proxy password to be applied.
+pub fn configure_builder<R: Runtime>(
+    app_handle: &AppHandle<R>,
+    builder: reqwest::ClientBuilder,
+) -> Result<reqwest::ClientBuilder, String> {
+    configure_client(
+        builder,
+        &load_settings(app_handle),
+        load_password().as_deref(),
+    )
+}
+
+/// Apply the stored proxy settings to the shared HTTP client
+pub fn apply_stored_settings(app_handle: &tauri::AppHandle) -> Result<(), String> {
+    let settings = load_settings(app_handle);
+    let client = crate::http_client::get_http_client().ok_or("HTTP client not initialized")?;
+    client
+        .apply_proxy(&settings, load_password().as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// Whether two settings point at the same proxy with the same account
+fn same_endpoint(a: &ProxySettings, b: &ProxySettings) -> bool {
+    a.kind == b.kind
+        && a.host.trim().eq_ignore_ascii_case(b.host.trim())
+        && a.port == b.port
+        && a.username == b.username
+}
+
+fn validate_settings(settings: &ProxySettings) -> Result<(), String> {
+    crate::security::validate_user_input(&settings.host, "proxy host", 255)?;
+    if let Some(username) = &settings.username {
+        crate::security::validate_user_input(username, "proxy username", 255)?;
+    }
+    if settings.bypass_hosts.len() > MAX_BYPASS_RULES
+        || settings.bypass_modules.len() > MAX_BYPASS_RULES
+    {
+        return Err(tr_args(
+            "proxy-too-many-bypass-rules",
+            &[("max", MAX_BYPASS_RULES.into())],
+        ));
+    }
+    for rule in settings
+        .bypass_hosts
+        .iter()
+        .chain(settings.bypass_modules.iter())
+    {
+        crate::security::validate_user_input(rule, "bypass rule", 255)?;
+    }
+    Ok(())
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn proxy_get_settings(app_handle: tauri::AppHandle) -> Result<ProxySettings, String> {
+    Ok(load_settings(&app_handle))
+}
+
+#[tauri::command]
+pub async fn proxy_set_settings(
+    app_handle: tauri::AppHandle,
+    settings: ProxySettings,
+    password: Option<String>,
+) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    validate_settings(&settings).map_err(|e| format!("Invalid proxy settings: {}", e))?;
+    if let Some(password) = password.as_deref().filter(|p| !p.is_empty()) {
+        crate::security::validate_user_input(password, "proxy password", 255)
+            .map_err(|e| format!("Invalid proxy password: {}", e))?;
+    }
+
+    let storage =
+        crate::secure_storage::get_secure_storage().ok_or("Secure storage not initialized")?;
+    let client = crate::http_client::get_http_client().ok_or("HTTP client not initialized")?;
+
+    // `None` keeps the stored password, an empty string clears it
+    let effective_password = match password.as_deref() {
+        Some("") => None,
+        Some(password) => Some(password.to_string()),
+        None => load_password(),
+    };
+
+    // Validate by applying before persisting, so settings that can't be turned
+    // into a proxy are never stored
+    client
+        .apply_proxy(&settings, effective_password.as_deref())
+        .map_err(|e| format!("Invalid proxy settings: {}", e))?;
+    crate::dns::reconfigure(&settings, effective_password.as_deref())?;
+
+    match password.as_deref() {
+        Some("") => {
+            storage
+                .remove(PROXY_PASSWORD_KEY)
+                .map_err(|e| e.to_string())?;
+        }
+        Some(password) => {
+            storage
+                .store(PROXY_PASSWORD_KEY, password)
+                .map_err(|e| e.to_string())?;
+        }
+        None => {}
+    }
+
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    store.set(
+        PROXY_SETTINGS_FIELD,
+        serde_json::to_value(&settings).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn proxy_test(
+    app_handle: tauri::AppHandle,
+    settings: Option<ProxySettings>,
+    password: Option<String>,
+) -> Result<ProxyTestResult, String> {
+    // Test the given settings, or the stored ones when none are passed
+    let (settings, password) = match settings {
+        Some(settings) => {
+            validate_settings(&settings).map_err(|e| format!("Invalid proxy settings: {}", e))?;
+            // The stored password may only go to the proxy it was saved for
+            let password = match password {
+                Some(password) => Some(password),
+                None if same_endpoint(&settings, &load_settings(&app_handle)) => load_password(),
+                None => None,
+            };
+            (settings, password)
+        }
+        None => (load_settings(&app_handle), load_password()),
+    };
+
+    let builder = reqwest::Client::builder().timeout(Duration::from_secs(15));
+    let client = configure_client(builder, &settings, password.as_deref())?
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    #[derive(Deserialize)]
+    struct ExitIp {
+        ip: String,
+    }
+
+    let started = Instant::now();
+    let outcome = async {
+        let response = client
+            .get(EXIT_IP_ENDPOINT)
+            .send()
+            .await
+            .map_err(|e| e.to_string())?
+            .error_for_status()
+            .map_err(|e| e.to_string())?;
+        response.json::<ExitIp>().await.map_err(|e| e.to_string())
+    }
+    .await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+
+    Ok(match outcome {
+        Ok(exit) => ProxyTestResult {
+            ok: true,
+            exit_ip: Some(exit.ip),
+            latency_ms,
+            error: None,
+        },
+        Err(e) => ProxyTestResult {
+            ok: false,
+            exit_ip: None,
+            latency_ms,
+            error: Some(e),
+        },
+    })
+}
@@ -6,6 +6,7 @@
 pub const MAX_STORAGE_VALUE_LENGTH: usize = 8192; // 8KB limit for secrets
 pub const REPEATED_CHAR_LENGTH: usize = 256; // Limit for potentially abusive repeated chars
 pub const LARGE_TEST_DATA_SIZE: usize = 1024 * 1024; // 1MB test data
+pub const MAX_URL_LENGTH: usize = 2048; // Limit for remote URLs passed from the frontend
 
 /// Validate user input to prevent excessive length or invalid characters
 ///
@@ -0,0 +1,323 @@
+/// Shortcuts Module
+///
+/// Stores the user's keyboard shortcut bindings for the shortcut editor,
+/// validates accelerators, detects conflicts, and keeps the global (system-wide)
+/// bindings registered. In-app bindings are handled by the frontend; global
+/// ones emit `shortcut-triggered` with the bound action.
+///
+/// Changes are applied atomically: if any global binding fails to register,
+/// the previous set is registered again and nothing is saved.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Modifiers, Shortcut};
+use tauri_plugin_store::StoreExt;
+
+use crate::i18n::{tr, tr_args};
+
+/// Event emitted when a global shortcut is pressed
+pub const SHORTCUT_TRIGGERED_EVENT: &str = "shortcut-triggered";
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding the bindings, keyed by action
+const SHORTCUTS_FIELD: &str = "shortcuts";
+
+/// Longest accepted action name or accelerator
+const MAX_SHORTCUT_LENGTH: usize = 64;
+
+/// A key combination bound to an action
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShortcutBinding {
+    /// Accelerator such as `CmdOrCtrl+Shift+K`
+    pub accelerator: String,
+    /// Registered system-wide rather than only while the app is focused
+    pub global: bool,
+}
+
+/// Why a binding can't be used
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", tag = "kind")]
+pub enum ShortcutConflict {
+    /// Already bound to another action
+    Action { action: String },
+    /// Already registered as a global shortcut outside these bindings
+    Registered,
+}
+
+/// Global shortcuts currently registered by us, with their actions
+static REGISTERED: Mutex<Vec<(String, Shortcut)>> = Mutex::new(Vec::new());
+
+/// Serializes binding changes so re-registration is never interleaved
+static UPDATE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Parse and validate an accelerator
+///
+/// Global shortcuts must include Ctrl, Alt or Super so they can't swallow
+/// ordinary typing in other applications.
+pub fn parse_accelerator(accelerator: &str, global: bool) -> Result<Shortcut, String> {
+    let shortcut = Shortcut::from_str(accelerator).map_err(|e| {
+        tr_args(
+            "shortcut-invalid",
+            &[
+                ("accelerator", accelerator.into()),
+                ("error", e.to_string().into()),
+            ],
+        )
+    })?;
+
+    if global
+        && !shortcut
+            .mods
+            .intersects(Modifiers::CONTROL | Modifiers::ALT | Modifiers::SUPER)
+    {
+        return Err(tr_args(
+            "shortcut-global-needs-modifier",
+            &[("accelerator", accelerator.into())],
+        ));
+    }
+
+    Ok(shortcut)
+}
+
+fn valid_action(action: &str) -> bool {
+    !action.is_empty()
+        && action.len() <= MAX_SHORTCUT_LENGTH
+        && action
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+}
+
+/// Stored bindings, keyed by action
+pub fn bindings<R: Runtime>(app: &AppHandle<R>) -> BTreeMap<String, ShortcutBinding> {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(SHORTCUTS_FIELD))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Find what a binding would conflict with
+///
+/// # Arguments
+/// * `action` - Action being bound; its own current binding is ignored
+/// * `shortcut` - Parsed accelerator
+/// * `global` - Whether it would be registered system-wide
+pub fn find_conflict<R: Runtime>(
+    app: &AppHandle<R>,
+    action: &str,
+    shortcut: &Shortcut,
+    global: bool,
+) -> Option<ShortcutConflict> {
+    for (other, binding) in bindings(app) {
+        if other == action {
+            continue;
+        }
+        let Ok(existing) = Shortcut::from_str(&binding.accelerator) else {
+            continue;
+        };
+        if existing.mods == shortcut.mods && existing.key == shortcut.key {
+            return Some(ShortcutConflict::Action { action: other });
+        }
+    }
+
+    // Registered for this action is fine. Shortcuts held by other applications
+    // aren't visible here; they only show up when registration fails
+    if global {
+        let ours = REGISTERED
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|(other, registered)| other == action && registered.id() == shortcut.id());
+        if !ours && app.global_shortcut().is_registered(*shortcut) {
+            return Some(ShortcutConflict::Registered);
+        }
+    }
+
+    None
+}
+
+/// Register a set of global bindings in place of the current ones
+///
+/// On failure the previous set is registered again. `REGISTERED` isn't held
+/// while registering, since registration runs on the main thread, which is
+/// also where `handle_global` reads it.
+fn apply_global<R: Runtime>(
+    app: &AppHandle<R>,
+    next: Vec<(String, Shortcut)>,
+) -> Result<(), String> {
+    let global_shortcut = app.global_shortcut();
+    let previous = REGISTERED.lock().unwrap().clone();
+
+    for (_, shortcut) in &previous {
+        let _ = global_shortcut.unregister(*shortcut);
+    }
+
+    let mut added = Vec::new();
+    for (action, shortcut) in &next {
+        if let Err(e) = global_shortcut.register(*shortcut) {
+            for shortcut in &added {
+                let _ = global_shortcut.unregister(*shortcut);
+            }
+            for (_, shortcut) in &previous {
+                let _ = global_shortcut.register(*shortcut);
+            }
+            return Err(tr_args(
+                "shortcut-register-failed",
+                &[
+                    ("action", action.as_str().into()),
+                    ("error", e.to_string().into()),
+                ],
+            ));
+        }
+        added.push(*shortcut);
+    }
+
+    *REGISTERED.lock().unwrap() = next;
+    Ok(())
+}
+
+/// Global bindings from a binding map, skipping any that no longer parse
+fn global_shortcuts(bindings: &BTreeMap<String, ShortcutBinding>) -> Vec<(String, Shortcut)> {
+    bindings
+        .iter()
+        .filter(|(_, binding)| binding.global)
+        .filter_map(|(action, binding)| {
+            parse_accelerator(&binding.accelerator, true)
+                .ok()
+                .map(|shortcut| (action.clone(), shortcut))
+        })
+        .collect()
+}
+
+/// Register the stored global bindings at startup
+///
+/// Unlike edits, one binding that can't be registered (e.g. taken by another
+/// application since it was saved) doesn't stop the others.
+pub fn init<R: Runtime>(app: &AppHandle<R>) {
+    let _guard = UPDATE_LOCK.lock().unwrap();
+    let mut registered = Vec::new();
+    for (action, shortcut) in global_shortcuts(&bindings(app)) {
+        match app.global_shortcut().register(shortcut) {
+            Ok(()) => registered.push((action, shortcut)),
+            Err(e) => tracing::warn!("Failed to register shortcut for '{}': {}", action, e),
+        }
+    }
+    *REGISTERED.lock().unwrap() = registered;
+}
+
+/// Bind, rebind or unbind an action
+///
+/// # Arguments
+/// * `action` - Action name
+/// * `binding` - New binding, or `None` to remove it
+///
+/// # Returns
+/// * `Ok(())` if the binding was saved and global bindings re-registered
+/// * `Err(String)` if it's invalid, conflicts, or couldn't be registered
+pub fn set_binding<R: Runtime>(
+    app: &AppHandle<R>,
+    action: &str,
+    binding: Option<ShortcutBinding>,
+) -> Result<(), String> {
+    let _guard = UPDATE_LOCK.lock().unwrap();
+    let mut next = bindings(app);
+
+    match binding {
+        Some(binding) => {
+            let shortcut = parse_accelerator(&binding.accelerator, binding.global)?;
+            match find_conflict(app, action, &shortcut, binding.global) {
+                Some(ShortcutConflict::Action { action: other }) => {
+                    return Err(tr_args("shortcut-in-use", &[("action", other.into())]));
+                }
+                Some(ShortcutConflict::Registered) => {
+                    return Err(tr("shortcut-registered-globally"));
+                }
+                None => {}
+            }
+            next.insert(action.to_string(), binding);
+        }
+        None => {
+            next.remove(action);
+        }
+    }
+
+    apply_global(app, global_shortcuts(&next))?;
+
+    let store = app.store(SETTINGS_STORE_NAME).map_err(|e| e.to_string())?;
+    store.set(
+        SHORTCUTS_FIELD,
+        serde_json::to_value(&next).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())
+}
+
+/// Emit the action bound to a pressed global shortcut
+pub fn handle_global<R: Runtime>(app: &AppHandle<R>, shortcut: &Shortcut) {
+    let action = REGISTERED
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(_, registered)| registered.id() == shortcut.id())
+        .map(|(action, _)| action.clone());
+
+    if let Some(action) = action {
+        let _ = app.emit(SHORTCUT_TRIGGERED_EVENT, action);
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn shortcuts_get(
+    app_handle: tauri::AppHandle,
+) -> Result<BTreeMap<String, ShortcutBinding>, String> {
+    Ok(bindings(&app_handle))
+}
+
+#[tauri::command]
+pub async fn shortcuts_set(
+    app_handle: tauri::AppHandle,
+    action: String,
+    binding: Option<ShortcutBinding>,
+) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    if !valid_action(&action) {
+        return Err("Invalid action".to_string());
+    }
+    if let Some(binding) = &binding {
+        crate::security::validate_user_input(
+            &binding.accelerator,
+            "accelerator",
+            MAX_SHORTCUT_LENGTH,
+        )
+        .map_err(|e| format!("Invalid accelerator: {}", e))?;
+    }
+
+    set_binding(&app_handle, &action, binding)
+}
+
+#[tauri::command]
+pub async fn shortcuts_check(
+    app_handle: tauri::AppHandle,
+    action: String,
+    binding: ShortcutBinding,
+) -> Result<Option<ShortcutConflict>, String> {
+    // SECURITY: Validate input parameters
+    if !valid_action(&action) {
+        return Err("Invalid action".to_string());
+    }
+    crate::security::validate_user_input(&binding.accelerator, "accelerator", MAX_SHORTCUT_LENGTH)
+        .map_err(|e| format!("Invalid accelerator: {}", e))?;
+
+    let shortcut = parse_accelerator(&binding.accelerator, binding.global)?;
+    Ok(find_conflict(
+        &app_handle,
+        &action,
+        &shortcut,
+        binding.global,
+    ))
+}
@@ -0,0 +1,216 @@
+/// System Report Module
+///
+/// Collects OS, CPU, GPU, memory, disk and webview details for bug reports
+/// and for choosing sensible defaults. Everything is best-effort: fields the
+/// platform can't provide are left empty rather than failing the report.
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::Command;
+use tauri::{AppHandle, Manager, Runtime};
+
+/// Hardware and software details of the running system
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemReport {
+    pub app_version: String,
+    pub os_name: Option<String>,
+    pub os_version: Option<String>,
+    pub kernel_version: Option<String>,
+    pub arch: String,
+    pub cpu_brand: Option<String>,
+    pub cpu_threads: usize,
+    pub total_memory_bytes: u64,
+    pub available_memory_bytes: u64,
+    pub gpus: Vec<String>,
+    /// Codecs with hardware decode support, if the platform exposes them
+    pub hardware_decoders: Option<Vec<String>>,
+    /// Space on the volume holding the app data directory
+    pub data_dir_free_bytes: Option<u64>,
+    pub data_dir_total_bytes: Option<u64>,
+    pub webview_version: Option<String>,
+}
+
+/// Build a system report
+///
+/// Runs platform tools (`lspci`, `vainfo`, `system_profiler`, PowerShell), so
+/// call it off the async runtime.
+pub fn collect<R: Runtime>(app: &AppHandle<R>) -> SystemReport {
+    let mut system = sysinfo::System::new();
+    system.refresh_memory();
+    system.refresh_cpu_all();
+
+    let (data_dir_free_bytes, data_dir_total_bytes) = match app.path().app_data_dir() {
+        Ok(data_dir) => disk_space(&data_dir),
+        Err(_) => (None, None),
+    };
+
+    SystemReport {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        os_name: sysinfo::System::name(),
+        os_version: sysinfo::System::long_os_version(),
+        kernel_version: sysinfo::System::kernel_version(),
+        arch: std::env::consts::ARCH.to_string(),
+        cpu_brand: system
+            .cpus()
+            .first()
+            .map(|cpu| cpu.brand().trim().to_string())
+            .filter(|brand| !brand.is_empty()),
+        cpu_threads: system.cpus().len(),
+        total_memory_bytes: system.total_memory(),
+        available_memory_bytes: system.available_memory(),
+        gpus: gpus(),
+        hardware_decoders: hardware_decoders(),
+        data_dir_free_bytes,
+        data_dir_total_bytes,
+        webview_version: tauri::webview_version().ok(),
+    }
+}
+
+/// Free and total space of the disk with the longest mount point containing `path`
+fn disk_space(path: &Path) -> (Option<u64>, Option<u64>) {
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    disks
+        .list()
+        .iter()
+        .filter(|disk| path.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len())
+        .map(|disk| (Some(disk.available_space()), Some(disk.total_space())))
+        .unwrap_or((None, None))
+}
+
+/// Run a command and return its stdout, if it succeeded
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let mut command = Command::new(program);
+    command.args(args);
+
+    // Release builds have no console, so each console tool would flash one
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x0800_0000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+/// GPU adapters, with the driver in use where the platform reports it
+#[cfg(target_os = "linux")]
+pub(crate) fn gpus() -> Vec<String> {
+    // 00:02.0 VGA compatible controller: Intel Corporation ... (rev 0c)
+    // 	Kernel driver in use: i915
+    let Some(output) = command_output("lspci", &["-k"]) else {
+        return Vec::new();
+    };
+
+    let mut adapters = Vec::new();
+    let mut current: Option<String> = None;
+    for line in output.lines() {
+        if !line.starts_with(char::is_whitespace) {
+            if let Some(adapter) = current.take() {
+                adapters.push(adapter);
+            }
+            let is_display = line.contains("VGA compatible controller")
+                || line.contains("3D controller")
+                || line.contains("Display controller");
+            if is_display {
+                current = line.splitn(3, ": ").nth(1).map(|s| s.trim().to_string());
+            }
+        } else if let Some(driver) = line.trim().strip_prefix("Kernel driver in use: ") {
+            if let Some(adapter) = current.as_mut() {
+                adapter.push_str(&format!(" (driver: {})", driver));
+            }
+        }
+    }
+    adapters.extend(current);
+    adapters
+}
+
+#[cfg(target_os = "macos")]
+pub(crate) fn gpus() -> Vec<String> {
+    let Some(output) = command_output("system_profiler", &["SPDisplaysDataType", "-json"]) else {
+        return Vec::new();
+    };
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&output) else {
+        return Vec::new();
+    };
+
+    json["SPDisplaysDataType"]
+        .as_array()
+        .map(|displays| {
+            displays
+                .iter()
+                .filter_map(|display| display["sppci_model"].as_str())
+                .map(|model| model.to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "windows")]
+pub(crate) fn gpus() -> Vec<String> {
+    command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "Get-CimInstance Win32_VideoController | ForEach-Object { \"$($_.Name) (driver: $($_.DriverVersion))\" }",
+        ],
+    )
+    .map(|output| {
+        output
+            .lines()
+            .map(|line| line.trim().to_string())
+            .filter(|line| !line.is_empty())
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+pub(crate) fn gpus() -> Vec<String> {
+    Vec::new()
+}
+
+/// Decode profiles reported by VA-API, when `vainfo` is installed
+#[cfg(target_os = "linux")]
+pub(crate) fn hardware_decoders() -> Option<Vec<String>> {
+    let output = command_output("vainfo", &[])?;
+
+    // VAProfileH264High               : VAEntrypointVLD
+    let mut codecs: Vec<String> = output
+        .lines()
+        .filter(|line| line.contains("VAEntrypointVLD"))
+        .filter_map(|line| {
+            let profile = line.trim().strip_prefix("VAProfile")?;
+            let codec = ["H264", "HEVC", "VP8", "VP9", "AV1", "MPEG2", "VC1", "JPEG"]
+                .into_iter()
+                .find(|codec| profile.starts_with(codec))?;
+            Some(codec.to_string())
+        })
+        .collect();
+    codecs.sort();
+    codecs.dedup();
+    Some(codecs)
+}
+
+/// Not exposed without platform media APIs; the webview's MediaCapabilities
+/// is the better source there
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn hardware_decoders() -> Option<Vec<String>> {
+    None
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn system_report(app_handle: tauri::AppHandle) -> Result<SystemReport, String> {
+    tauri::async_runtime::spawn_blocking(move || collect(&app_handle))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,220 @@
+/// Telemetry Module
+///
+/// Opt-in OpenTelemetry export for diagnosing performance. When enabled, the
+/// `tracing` spans emitted by the network stack (HTTP requests, DoH lookups,
+/// thumbnail fetches) are exported as traces and the HTTP client counters as
+/// metrics, both over OTLP/HTTP to a user-supplied collector. Nothing is sent
+/// unless the user turns it on; changes take effect on the next launch.
+use opentelemetry::metrics::MeterProvider as _;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::{WithExportConfig, WithHttpConfig};
+use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::{runtime, Resource};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+use tracing::Subscriber;
+use tracing_subscriber::registry::LookupSpan;
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding `TelemetrySettings`
+const TELEMETRY_SETTINGS_FIELD: &str = "telemetry_settings";
+
+/// Service name reported to the collector
+const SERVICE_NAME: &str = "youtube.pub";
+
+/// Opt-in OTLP export settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+    /// Collector base URL, e.g. `http://localhost:4318`
+    pub endpoint: String,
+}
+
+/// Providers kept so buffered data can be flushed on exit
+struct Providers {
+    tracer: TracerProvider,
+    meter: SdkMeterProvider,
+}
+
+static PROVIDERS: once_cell::sync::OnceCell<Providers> = once_cell::sync::OnceCell::new();
+
+/// Tracing layer exporting spans to the collector
+pub type TelemetryLayer<S> =
+    tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>;
+
+/// Load the stored telemetry settings, falling back to disabled
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> TelemetrySettings {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(TELEMETRY_SETTINGS_FIELD))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Build the tracing layer exporting spans, if telemetry is enabled
+///
+/// Also installs the metrics pipeline. Called once while the subscriber is
+/// being assembled.
+///
+/// # Returns
+/// * `Ok(Some(layer))` when enabled and the exporters could be created
+/// * `Ok(None)` when disabled or no valid endpoint is configured
+/// * `Err(String)` if the exporters could not be created
+pub fn layer<S, R: Runtime>(app: &AppHandle<R>) -> Result<Option<TelemetryLayer<S>>, String>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let settings = load_settings(app);
+    if !settings.enabled || validate_endpoint(&settings.endpoint).is_err() {
+        return Ok(None);
+    }
+
+    // The batch exporters spawn onto the current Tokio runtime
+    let _runtime = tauri::async_runtime::handle().inner().enter();
+
+    let endpoint = settings.endpoint.trim_end_matches('/');
+    let resource = Resource::new(vec![
+        KeyValue::new("service.name", SERVICE_NAME),
+        KeyValue::new("service.version", env!("CARGO_PKG_VERSION")),
+    ]);
+
+    // Export through the configured proxy like every other request
+    let http_client = crate::proxy::configure_builder(app, reqwest::Client::builder())
+        .and_then(|builder| builder.build().map_err(|e| e.to_string()))
+        .map_err(|e| format!("Failed to create OTLP HTTP client: {}", e))?;
+
+    let span_exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_http_client(http_client.clone())
+        .with_endpoint(format!("{}/v1/traces", endpoint))
+        .build()
+        .map_err(|e| format!("Failed to create OTLP span exporter: {}", e))?;
+    let tracer_provider = TracerProvider::builder()
+        .with_batch_exporter(span_exporter, runtime::Tokio)
+        .with_resource(resource.clone())
+        .build();
+
+    let metric_exporter = opentelemetry_otlp::MetricExporter::builder()
+        .with_http()
+        .with_http_client(http_client)
+        .with_endpoint(format!("{}/v1/metrics", endpoint))
+        .build()
+        .map_err(|e| format!("Failed to create OTLP metric exporter: {}", e))?;
+    let meter_provider = SdkMeterProvider::builder()
+        .with_reader(PeriodicReader::builder(metric_exporter, runtime::Tokio).build())
+        .with_resource(resource)
+        .build();
+
+    register_network_metrics(&meter_provider);
+
+    let tracer = tracer_provider.tracer(SERVICE_NAME);
+    let _ = PROVIDERS.set(Providers {
+        tracer: tracer_provider,
+        meter: meter_provider,
+    });
+
+    Ok(Some(tracing_opentelemetry::layer().with_tracer(tracer)))
+}
+
+/// Export the HTTP client counters as observable counters
+fn register_network_metrics(provider: &SdkMeterProvider) {
+    let meter = provider.meter(SERVICE_NAME);
+
+    let counters: [(
+        &'static str,
+        &'static str,
+        fn(&crate::http_client::NetworkStats) -> u64,
+    ); 4] = [
+        (
+            "http.client.requests",
+            "Requests sent, including retries",
+            |s| s.requests,
+        ),
+        (
+            "http.client.retries",
+            "Attempts repeated after a transient failure",
+            |s| s.retries,
+        ),
+        (
+            "http.client.failures",
+            "Requests that failed after all retries",
+            |s| s.failures,
+        ),
+        (
+            "http.client.circuit_rejections",
+            "Requests rejected by an open circuit",
+            |s| s.circuit_rejections,
+        ),
+    ];
+
+    for (name, description, read) in counters {
+        meter
+            .u64_observable_counter(name)
+            .with_description(description)
+            .with_callback(move |observer| {
+                if let Some(client) = crate::http_client::get_http_client() {
+                    observer.observe(read(&client.stats()), &[]);
+                }
+            })
+            .build();
+    }
+}
+
+/// Flush and stop the exporters
+pub fn shutdown() {
+    if let Some(providers) = PROVIDERS.get() {
+        let _ = providers.tracer.shutdown();
+        let _ = providers.meter.shutdown();
+    }
+}
+
+/// Require an absolute http(s) collector URL
+fn validate_endpoint(endpoint: &str) -> Result<(), String> {
+    let url = url::Url::parse(endpoint).map_err(|e| e.to_string())?;
+    match url.scheme() {
+        "http" | "https" => Ok(()),
+        _ => Err("Endpoint must be an http(s) URL".to_string()),
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn telemetry_get_settings(
+    app_handle: tauri::AppHandle,
+) -> Result<TelemetrySettings, String> {
+    Ok(load_settings(&app_handle))
+}
+
+#[tauri::command]
+pub async fn telemetry_set_settings(
+    app_handle: tauri::AppHandle,
+    settings: TelemetrySettings,
+) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(
+        &settings.endpoint,
+        "endpoint",
+        crate::security::MAX_URL_LENGTH,
+    )
+    .map_err(|e| format!("Invalid endpoint: {}", e))?;
+    if settings.enabled {
+        validate_endpoint(&settings.endpoint).map_err(|e| format!("Invalid endpoint: {}", e))?;
+    }
+
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    store.set(
+        TELEMETRY_SETTINGS_FIELD,
+        serde_json::to_value(&settings).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
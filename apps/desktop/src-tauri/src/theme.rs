@@ -0,0 +1,270 @@
+/// Theme Module
+///
+/// Persists the appearance settings (theme, accent color and CSS variable
+/// overrides) in the settings store, under the same `app_theme` field the
+/// frontend settings page uses. Windows get the native theme at creation and
+/// the CSS variables on every page load; `theme-changed` is emitted when the
+/// settings change or, with the system theme selected, when the OS switches
+/// between light and dark.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tauri::webview::{PageLoadEvent, PageLoadPayload};
+use tauri::{
+    AppHandle, Emitter, Manager, Runtime, Theme, Webview, WebviewWindow, Window, WindowEvent,
+};
+use tauri_plugin_store::StoreExt;
+
+use crate::i18n::tr_args;
+
+/// Event emitted when the effective theme or its settings change
+pub const THEME_CHANGED_EVENT: &str = "theme-changed";
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding the theme choice
+const THEME_FIELD: &str = "app_theme";
+/// Settings store field holding the accent color
+const ACCENT_COLOR_FIELD: &str = "accent_color";
+/// Settings store field holding CSS variable overrides
+const THEME_OVERRIDES_FIELD: &str = "theme_overrides";
+
+/// CSS variable the accent color is exposed as
+const ACCENT_VARIABLE: &str = "--app-accent";
+
+/// Limits on overrides from the frontend
+const MAX_OVERRIDES: usize = 64;
+const MAX_OVERRIDE_LENGTH: usize = 128;
+
+/// Theme choice
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AppTheme {
+    Light,
+    Dark,
+    System,
+}
+
+/// Appearance settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeSettings {
+    pub theme: AppTheme,
+    /// `#rrggbb` or `#rgb`
+    pub accent_color: Option<String>,
+    /// CSS variable name (`--name`) to value
+    pub overrides: BTreeMap<String, String>,
+}
+
+/// Payload of `theme-changed`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeChanged {
+    #[serde(flatten)]
+    pub settings: ThemeSettings,
+    /// Light or dark after resolving `system`
+    pub resolved: AppTheme,
+}
+
+/// Load the stored settings; the theme defaults to dark like the frontend
+pub fn load_settings<R: Runtime>(app: &AppHandle<R>) -> ThemeSettings {
+    let store = app.store(SETTINGS_STORE_NAME).ok();
+    let get = |field: &str| store.as_ref().and_then(|store| store.get(field));
+
+    ThemeSettings {
+        theme: get(THEME_FIELD)
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or(AppTheme::Dark),
+        accent_color: get(ACCENT_COLOR_FIELD).and_then(|value| value.as_str().map(String::from)),
+        overrides: get(THEME_OVERRIDES_FIELD)
+            .and_then(|value| serde_json::from_value(value).ok())
+            .unwrap_or_default(),
+    }
+}
+
+fn valid_color(color: &str) -> bool {
+    let Some(hex) = color.strip_prefix('#') else {
+        return false;
+    };
+    matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn valid_variable(name: &str) -> bool {
+    name.len() > 2
+        && name.len() <= MAX_OVERRIDE_LENGTH
+        && name.starts_with("--")
+        && name[2..]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Values can't close the declaration or the element they're applied to
+fn valid_value(value: &str) -> bool {
+    value.len() <= MAX_OVERRIDE_LENGTH && !value.contains([';', '{', '}', '<', '>', '\\'])
+}
+
+fn validate(settings: &ThemeSettings) -> Result<(), String> {
+    if let Some(color) = &settings.accent_color {
+        if !valid_color(color) {
+            return Err(tr_args(
+                "theme-invalid-accent",
+                &[("color", color.as_str().into())],
+            ));
+        }
+    }
+    if settings.overrides.len() > MAX_OVERRIDES {
+        return Err(tr_args(
+            "theme-too-many-overrides",
+            &[("max", MAX_OVERRIDES.into())],
+        ));
+    }
+    for (name, value) in &settings.overrides {
+        if !valid_variable(name) {
+            return Err(tr_args(
+                "theme-invalid-variable",
+                &[("name", name.as_str().into())],
+            ));
+        }
+        if !valid_value(value) {
+            return Err(tr_args(
+                "theme-invalid-value",
+                &[("name", name.as_str().into())],
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn native_theme(theme: AppTheme) -> Option<Theme> {
+    match theme {
+        AppTheme::Light => Some(Theme::Light),
+        AppTheme::Dark => Some(Theme::Dark),
+        AppTheme::System => None,
+    }
+}
+
+/// Resolve `system` against a window's current OS theme
+fn resolve<R: Runtime>(theme: AppTheme, window: Option<&Window<R>>) -> AppTheme {
+    match theme {
+        AppTheme::System => match window.and_then(|window| window.theme().ok()) {
+            Some(Theme::Light) => AppTheme::Light,
+            _ => AppTheme::Dark,
+        },
+        theme => theme,
+    }
+}
+
+/// Apply the stored native theme to a window, e.g. right after it's created
+pub fn apply_to_window<R: Runtime>(window: &WebviewWindow<R>) {
+    let settings = load_settings(window.app_handle());
+    let _ = window.set_theme(native_theme(settings.theme));
+}
+
+/// CSS variables a settings value sets: overrides plus the accent
+fn css_variables(settings: &ThemeSettings) -> BTreeMap<String, String> {
+    let mut variables = settings.overrides.clone();
+    if let Some(color) = &settings.accent_color {
+        variables.insert(ACCENT_VARIABLE.to_string(), color.clone());
+    }
+    variables
+}
+
+/// Script that sets `variables` and removes `removed` on the document root
+fn css_script(variables: &BTreeMap<String, String>, removed: &[String]) -> Option<String> {
+    let variables = serde_json::to_string(variables).ok()?;
+    let removed = serde_json::to_string(removed).ok()?;
+    Some(format!(
+        "(() => {{ const style = document.documentElement.style; for (const name of {}) {{ style.removeProperty(name); }} for (const [name, value] of Object.entries({})) {{ style.setProperty(name, value); }} }})();",
+        removed, variables
+    ))
+}
+
+/// Apply the CSS variables once a page has loaded
+pub fn handle_page_load<R: Runtime>(webview: &Webview<R>, payload: &PageLoadPayload<'_>) {
+    if !matches!(payload.event(), PageLoadEvent::Finished) {
+        return;
+    }
+    let settings = load_settings(webview.app_handle());
+    if let Some(script) = css_script(&css_variables(&settings), &[]) {
+        let _ = webview.eval(&script);
+    }
+}
+
+fn emit_changed<R: Runtime>(app: &AppHandle<R>, settings: ThemeSettings) {
+    let window = app
+        .get_webview_window("main")
+        .map(|window| window.as_ref().window());
+    let resolved = resolve(settings.theme, window.as_ref());
+    let _ = app.emit(THEME_CHANGED_EVENT, ThemeChanged { settings, resolved });
+}
+
+/// Emit `theme-changed` when the OS theme changes and the system theme is selected
+pub fn handle_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if window.label() != "main" {
+        return;
+    }
+    if let WindowEvent::ThemeChanged(_) = event {
+        let settings = load_settings(window.app_handle());
+        if settings.theme == AppTheme::System {
+            emit_changed(window.app_handle(), settings);
+        }
+    }
+}
+
+/// Validate, store and apply new settings to every window
+pub fn save_settings<R: Runtime>(
+    app: &AppHandle<R>,
+    settings: ThemeSettings,
+) -> Result<(), String> {
+    validate(&settings)?;
+
+    // Variables set by the previous settings but not the new ones
+    let variables = css_variables(&settings);
+    let removed: Vec<String> = css_variables(&load_settings(app))
+        .into_keys()
+        .filter(|name| !variables.contains_key(name))
+        .collect();
+
+    let store = app.store(SETTINGS_STORE_NAME).map_err(|e| e.to_string())?;
+    store.set(
+        THEME_FIELD,
+        serde_json::to_value(settings.theme).map_err(|e| e.to_string())?,
+    );
+    match &settings.accent_color {
+        Some(color) => store.set(ACCENT_COLOR_FIELD, color.clone()),
+        None => {
+            store.delete(ACCENT_COLOR_FIELD);
+        }
+    }
+    store.set(
+        THEME_OVERRIDES_FIELD,
+        serde_json::to_value(&settings.overrides).map_err(|e| e.to_string())?,
+    );
+    store.save().map_err(|e| e.to_string())?;
+
+    let script = css_script(&variables, &removed);
+    for window in app.webview_windows().values() {
+        let _ = window.set_theme(native_theme(settings.theme));
+        if let Some(script) = &script {
+            let _ = window.eval(script);
+        }
+    }
+
+    emit_changed(app, settings);
+    Ok(())
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn theme_get(app_handle: tauri::AppHandle) -> Result<ThemeSettings, String> {
+    Ok(load_settings(&app_handle))
+}
+
+#[tauri::command]
+pub async fn theme_set(
+    app_handle: tauri::AppHandle,
+    settings: ThemeSettings,
+) -> Result<(), String> {
+    // SECURITY: Validate input parameters (see `validate`)
+    save_settings(&app_handle, settings)
+}
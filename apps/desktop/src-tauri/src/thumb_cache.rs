@@ -0,0 +1,466 @@
+use crate::security::*;
+/// Thumbnail Cache Module
+///
+/// Content-addressed disk cache for YouTube thumbnails and channel avatars.
+/// Images are stored under the SHA-256 of their bytes and served to the webview
+/// through the `thumb://` URI scheme, with least-recently-used eviction once the
+/// cache grows past its size limit.
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::http::{Request, Response, StatusCode};
+
+/// Maximum total size of cached images per kind before eviction kicks in
+pub const DEFAULT_MAX_CACHE_BYTES: u64 = 256 * 1024 * 1024; // 256MB
+
+/// Hosts the cache is allowed to fetch from on a miss
+const ALLOWED_HOSTS: &[&str] = &[
+    "i.ytimg.com",
+    "i1.ytimg.com",
+    "i2.ytimg.com",
+    "i3.ytimg.com",
+    "i4.ytimg.com",
+    "i9.ytimg.com",
+    "img.youtube.com",
+    "yt3.ggpht.com",
+    "yt3.googleusercontent.com",
+];
+
+/// Custom error type for thumbnail cache operations
+#[derive(Debug)]
+pub enum ThumbCacheError {
+    InvalidKind(String),
+    InvalidSource(String),
+    FetchFailed(String),
+    IoError(std::io::Error),
+}
+
+impl fmt::Display for ThumbCacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThumbCacheError::InvalidKind(msg) => write!(f, "Invalid cache kind: {}", msg),
+            ThumbCacheError::InvalidSource(msg) => write!(f, "Invalid source: {}", msg),
+            ThumbCacheError::FetchFailed(msg) => write!(f, "Fetch failed: {}", msg),
+            ThumbCacheError::IoError(err) => write!(f, "IO error: {}", err),
+        }
+    }
+}
+
+impl Error for ThumbCacheError {}
+
+impl From<std::io::Error> for ThumbCacheError {
+    fn from(err: std::io::Error) -> Self {
+        ThumbCacheError::IoError(err)
+    }
+}
+
+/// Result type for thumbnail cache operations
+pub type ThumbCacheResult<T> = Result<T, ThumbCacheError>;
+
+/// Kind of cached image, each stored and evicted independently
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheKind {
+    Thumbnails,
+    Avatars,
+}
+
+impl CacheKind {
+    pub const ALL: [CacheKind; 2] = [CacheKind::Thumbnails, CacheKind::Avatars];
+
+    /// Parse a kind from its path segment / command argument
+    pub fn parse(value: &str) -> ThumbCacheResult<Self> {
+        match value {
+            "thumbnails" => Ok(CacheKind::Thumbnails),
+            "avatars" => Ok(CacheKind::Avatars),
+            other => Err(ThumbCacheError::InvalidKind(other.to_string())),
+        }
+    }
+
+    fn dir_name(&self) -> &'static str {
+        match self {
+            CacheKind::Thumbnails => "thumbnails",
+            CacheKind::Avatars => "avatars",
+        }
+    }
+}
+
+/// Summary of a purge operation
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PurgeResult {
+    /// Number of cached images removed
+    pub files_removed: u64,
+    /// Bytes freed on disk
+    pub bytes_freed: u64,
+}
+
+/// Thumbnail cache manager
+pub struct ThumbCacheManager {
+    /// Root directory of the cache
+    cache_dir: PathBuf,
+    /// Size limit per kind in bytes
+    max_bytes: u64,
+    /// Current size of each kind in bytes, indexed like `CacheKind::ALL`
+    sizes: Mutex<[u64; 2]>,
+}
+
+impl ThumbCacheManager {
+    /// Open (or create) the thumbnail cache
+    ///
+    /// # Arguments
+    /// * `app_cache_dir` - Application cache directory
+    /// * `max_bytes` - Size limit per kind in bytes
+    ///
+    /// # Returns
+    /// * `Ok(ThumbCacheManager)` if initialization succeeds
+    /// * `Err(ThumbCacheError)` if the cache directories cannot be created
+    pub fn new(app_cache_dir: &PathBuf, max_bytes: u64) -> ThumbCacheResult<Self> {
+        let cache_dir = app_cache_dir.join("thumb_cache");
+        let mut sizes = [0u64; 2];
+
+        for (index, kind) in CacheKind::ALL.iter().enumerate() {
+            let objects_dir = cache_dir.join(kind.dir_name()).join("objects");
+            fs::create_dir_all(&objects_dir)?;
+            fs::create_dir_all(cache_dir.join(kind.dir_name()).join("refs"))?;
+            sizes[index] = Self::list_objects(&objects_dir)
+                .iter()
+                .map(|(_, size, _)| size)
+                .sum();
+        }
+
+        Ok(Self {
+            cache_dir,
+            max_bytes,
+            sizes: Mutex::new(sizes),
+        })
+    }
+
+    /// Look up a cached image by its source URL
+    ///
+    /// Marks the entry as recently used on a hit.
+    ///
+    /// # Returns
+    /// * `Ok(Some(bytes))` on a cache hit
+    /// * `Ok(None)` on a miss
+    pub fn get(&self, kind: CacheKind, source_url: &str) -> ThumbCacheResult<Option<Vec<u8>>> {
+        let ref_path = self.ref_path(kind, source_url);
+        if !ref_path.exists() {
+            return Ok(None);
+        }
+
+        let content_hash = fs::read_to_string(&ref_path)?;
+        let object_path = self.object_path(kind, content_hash.trim());
+
+        // The object may have been evicted while the ref survived
+        if !object_path.exists() {
+            let _ = fs::remove_file(&ref_path);
+            return Ok(None);
+        }
+
+        let bytes = fs::read(&object_path)?;
+        if let Ok(file) = fs::File::options().write(true).open(&object_path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+
+        Ok(Some(bytes))
+    }
+
+    /// Store an image for a source URL, evicting old entries if needed
+    pub fn put(&self, kind: CacheKind, source_url: &str, bytes: &[u8]) -> ThumbCacheResult<()> {
+        let content_hash = hex::encode(Sha256::digest(bytes));
+        let object_path = self.object_path(kind, &content_hash);
+
+        if !object_path.exists() {
+            fs::write(&object_path, bytes)?;
+            let mut sizes = self.sizes.lock().unwrap();
+            sizes[Self::index(kind)] += bytes.len() as u64;
+        }
+        fs::write(self.ref_path(kind, source_url), &content_hash)?;
+
+        self.evict(kind)
+    }
+
+    /// Fetch an image through the cache, downloading it on a miss
+    ///
+    /// # Arguments
+    /// * `kind` - Kind of image
+    /// * `source_url` - Remote URL on one of the allowed YouTube image hosts
+    #[tracing::instrument(name = "thumb_cache.fetch", skip(self, source_url))]
+    pub async fn fetch(&self, kind: CacheKind, source_url: &str) -> ThumbCacheResult<Vec<u8>> {
+        if let Some(bytes) = self.get(kind, source_url)? {
+            return Ok(bytes);
+        }
+
+        validate_source_url(source_url)?;
+
+        let client = crate::http_client::get_http_client().ok_or_else(|| {
+            ThumbCacheError::FetchFailed("HTTP client not initialized".to_string())
+        })?;
+        let bytes = client
+            .get("thumb_cache", source_url)
+            .await
+            .map_err(|e| ThumbCacheError::FetchFailed(e.to_string()))?
+            .bytes()
+            .await
+            .map_err(|e| ThumbCacheError::FetchFailed(e.to_string()))?;
+        self.put(kind, source_url, &bytes)?;
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Remove every cached image of one kind
+    pub fn purge(&self, kind: CacheKind) -> ThumbCacheResult<PurgeResult> {
+        let kind_dir = self.cache_dir.join(kind.dir_name());
+        let objects = Self::list_objects(&kind_dir.join("objects"));
+
+        let result = PurgeResult {
+            files_removed: objects.len() as u64,
+            bytes_freed: objects.iter().map(|(_, size, _)| size).sum(),
+        };
+
+        fs::remove_dir_all(&kind_dir)?;
+        fs::create_dir_all(kind_dir.join("objects"))?;
+        fs::create_dir_all(kind_dir.join("refs"))?;
+
+        let mut sizes = self.sizes.lock().unwrap();
+        sizes[Self::index(kind)] = 0;
+
+        Ok(result)
+    }
+
+    /// Evict least-recently-used objects until the kind fits its limit
+    fn evict(&self, kind: CacheKind) -> ThumbCacheResult<()> {
+        let mut sizes = self.sizes.lock().unwrap();
+        let index = Self::index(kind);
+        if sizes[index] <= self.max_bytes {
+            return Ok(());
+        }
+
+        let mut objects = Self::list_objects(&self.cache_dir.join(kind.dir_name()).join("objects"));
+        objects.sort_by_key(|(_, _, modified)| *modified);
+
+        for (path, size, _) in objects {
+            if sizes[index] <= self.max_bytes {
+                break;
+            }
+            fs::remove_file(&path)?;
+            sizes[index] = sizes[index].saturating_sub(size);
+        }
+
+        self.remove_dangling_refs(kind);
+        Ok(())
+    }
+
+    /// Remove refs whose object no longer exists
+    fn remove_dangling_refs(&self, kind: CacheKind) {
+        let kind_dir = self.cache_dir.join(kind.dir_name());
+        let Ok(entries) = fs::read_dir(kind_dir.join("refs")) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let dangling = match fs::read_to_string(entry.path()) {
+                Ok(content_hash) => !kind_dir.join("objects").join(content_hash.trim()).exists(),
+                Err(_) => true,
+            };
+            if dangling {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+
+    /// List objects in a directory as (path, size, last used)
+    fn list_objects(objects_dir: &PathBuf) -> Vec<(PathBuf, u64, SystemTime)> {
+        let mut objects = Vec::new();
+
+        if let Ok(entries) = fs::read_dir(objects_dir) {
+            for entry in entries.flatten() {
+                if let Ok(metadata) = entry.metadata() {
+                    if metadata.is_file() {
+                        let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                        objects.push((entry.path(), metadata.len(), modified));
+                    }
+                }
+            }
+        }
+
+        objects
+    }
+
+    fn object_path(&self, kind: CacheKind, content_hash: &str) -> PathBuf {
+        self.cache_dir
+            .join(kind.dir_name())
+            .join("objects")
+            .join(content_hash)
+    }
+
+    fn ref_path(&self, kind: CacheKind, source_url: &str) -> PathBuf {
+        let url_hash = hex::encode(Sha256::digest(source_url.as_bytes()));
+        self.cache_dir
+            .join(kind.dir_name())
+            .join("refs")
+            .join(url_hash)
+    }
+
+    fn index(kind: CacheKind) -> usize {
+        match kind {
+            CacheKind::Thumbnails => 0,
+            CacheKind::Avatars => 1,
+        }
+    }
+}
+
+/// Ensure a source URL points at a known YouTube image host over HTTPS
+fn validate_source_url(source_url: &str) -> ThumbCacheResult<()> {
+    validate_user_input(source_url, "source URL", MAX_URL_LENGTH)
+        .map_err(ThumbCacheError::InvalidSource)?;
+
+    let parsed =
+        url::Url::parse(source_url).map_err(|e| ThumbCacheError::InvalidSource(e.to_string()))?;
+
+    if parsed.scheme() != "https" {
+        return Err(ThumbCacheError::InvalidSource(
+            "Only https sources are allowed".to_string(),
+        ));
+    }
+
+    match parsed.host_str() {
+        Some(host) if ALLOWED_HOSTS.contains(&host) => Ok(()),
+        _ => Err(ThumbCacheError::InvalidSource(format!(
+            "Host not allowed: {}",
+            source_url
+        ))),
+    }
+}
+
+/// Keep image fetches on the allowed hosts when the server redirects them
+fn check_redirect(origin: &url::Url, next: &url::Url) -> Result<(), String> {
+    if validate_source_url(origin.as_str()).is_err() {
+        // Not an image fetch
+        return Ok(());
+    }
+    validate_source_url(next.as_str()).map_err(|e| e.to_string())
+}
+
+/// Guess an image MIME type from its magic bytes
+fn sniff_content_type(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        "image/jpeg"
+    } else if bytes.starts_with(&[0x89, b'P', b'N', b'G']) {
+        "image/png"
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else if bytes.starts_with(b"GIF8") {
+        "image/gif"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+/// Global thumbnail cache instance (using OnceCell for thread safety)
+static THUMB_CACHE: once_cell::sync::OnceCell<ThumbCacheManager> = once_cell::sync::OnceCell::new();
+
+/// Initialize the global thumbnail cache
+///
+/// # Arguments
+/// * `app_cache_dir` - Application cache directory
+///
+/// # Returns
+/// * `Ok(())` if initialization succeeds
+/// * `Err(ThumbCacheError)` if initialization fails
+pub fn init_thumb_cache(app_cache_dir: &PathBuf) -> ThumbCacheResult<()> {
+    let manager = ThumbCacheManager::new(app_cache_dir, DEFAULT_MAX_CACHE_BYTES)?;
+    // A second initialization keeps the existing cache
+    if THUMB_CACHE.set(manager).is_ok() {
+        crate::http_client::add_redirect_guard(check_redirect);
+    }
+    Ok(())
+}
+
+/// Get the global thumbnail cache instance
+pub fn get_thumb_cache() -> Option<&'static ThumbCacheManager> {
+    THUMB_CACHE.get()
+}
+
+/// Handle a `thumb://` request
+///
+/// URLs have the form `thumb://localhost/<kind>/<percent-encoded source URL>`
+/// (`http://thumb.localhost/...` on Windows).
+pub async fn handle_uri_request(request: Request<Vec<u8>>) -> Response<Vec<u8>> {
+    let path = request.uri().path().trim_start_matches('/').to_string();
+
+    let result = async {
+        let (kind, encoded_source) = path
+            .split_once('/')
+            .ok_or_else(|| ThumbCacheError::InvalidSource(path.clone()))?;
+        let kind = CacheKind::parse(kind)?;
+        let source_url = percent_encoding::percent_decode_str(encoded_source)
+            .decode_utf8()
+            .map_err(|e| ThumbCacheError::InvalidSource(e.to_string()))?
+            .to_string();
+
+        let cache = get_thumb_cache().ok_or_else(|| {
+            ThumbCacheError::FetchFailed("Thumbnail cache not initialized".to_string())
+        })?;
+        cache.fetch(kind, &source_url).await
+    }
+    .await;
+
+    match result {
+        Ok(bytes) => Response::builder()
+            .status(StatusCode::OK)
+            .header("Content-Type", sniff_content_type(&bytes))
+            .header("Cache-Control", "max-age=86400")
+            .body(bytes)
+            .unwrap(),
+        Err(e) => {
+            let status = match e {
+                ThumbCacheError::InvalidKind(_) | ThumbCacheError::InvalidSource(_) => {
+                    StatusCode::BAD_REQUEST
+                }
+                ThumbCacheError::FetchFailed(_) => StatusCode::BAD_GATEWAY,
+                ThumbCacheError::IoError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            Response::builder()
+                .status(status)
+                .header("Content-Type", "text/plain")
+                .body(e.to_string().into_bytes())
+                .unwrap()
+        }
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn cache_purge(
+    _app_handle: tauri::AppHandle,
+    kind: String,
+) -> Result<PurgeResult, String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(&kind, "cache kind", 32)
+        .map_err(|e| format!("Invalid cache kind: {}", e))?;
+
+    // Ensure thumbnail cache is initialized
+    let cache = get_thumb_cache().ok_or("Thumbnail cache not initialized")?;
+
+    let kinds = if kind == "all" {
+        CacheKind::ALL.to_vec()
+    } else {
+        vec![CacheKind::parse(&kind).map_err(|e| e.to_string())?]
+    };
+
+    let mut total = PurgeResult::default();
+    for kind in kinds {
+        let result = cache.purge(kind).map_err(|e| e.to_string())?;
+        total.files_removed += result.files_removed;
+        total.bytes_freed += result.bytes_freed;
+    }
+
+    Ok(total)
+}
@@ -0,0 +1,184 @@
+/// Thumbnail Palette Module
+///
+/// Extracts dominant and accent colors from a video thumbnail for ambient
+/// background theming. The thumbnail is loaded through the thumbnail cache,
+/// downscaled, and clustered with a small k-means in RGB space.
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::{tr, tr_args};
+use crate::thumb_cache::{get_thumb_cache, CacheKind};
+
+/// Number of clusters
+const PALETTE_SIZE: usize = 5;
+/// k-means iterations; the image is small enough that this converges
+const ITERATIONS: usize = 12;
+/// Width the thumbnail is downscaled to before clustering
+const SAMPLE_WIDTH: u32 = 64;
+/// Smallest share of pixels a cluster needs to be picked as the accent
+const MIN_ACCENT_WEIGHT: f32 = 0.05;
+
+/// One palette entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PaletteColor {
+    /// `#rrggbb`
+    pub hex: String,
+    /// Share of pixels in this cluster (0-1)
+    pub weight: f32,
+}
+
+/// Colors extracted from a thumbnail
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThumbPalette {
+    /// Most common color
+    pub dominant: String,
+    /// Most vivid color with a meaningful share of the image
+    pub accent: String,
+    /// All clusters, most common first
+    pub colors: Vec<PaletteColor>,
+}
+
+fn valid_video_id(video_id: &str) -> bool {
+    video_id.len() == 11
+        && video_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn to_hex(color: [f32; 3]) -> String {
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        color[0].round() as u8,
+        color[1].round() as u8,
+        color[2].round() as u8
+    )
+}
+
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)
+}
+
+/// Chroma: high for saturated mid-tones, low for greys and near-black/white
+fn vividness(color: [f32; 3]) -> f32 {
+    color[0].max(color[1]).max(color[2]) - color[0].min(color[1]).min(color[2])
+}
+
+/// Cluster pixels into `PALETTE_SIZE` colors, most common first
+fn cluster(pixels: &[[f32; 3]]) -> Vec<([f32; 3], usize)> {
+    // Deterministic seeds spread across the brightness range
+    let mut sorted = pixels.to_vec();
+    sorted.sort_by(|a, b| {
+        let la = a[0] + a[1] + a[2];
+        let lb = b[0] + b[1] + b[2];
+        la.total_cmp(&lb)
+    });
+    let mut centroids: Vec<[f32; 3]> = (0..PALETTE_SIZE)
+        .map(|i| sorted[(2 * i + 1) * sorted.len() / (2 * PALETTE_SIZE)])
+        .collect();
+
+    let mut assignments = vec![0usize; pixels.len()];
+    for _ in 0..ITERATIONS {
+        for (pixel, assignment) in pixels.iter().zip(assignments.iter_mut()) {
+            *assignment = (0..centroids.len())
+                .min_by(|&a, &b| {
+                    distance(*pixel, centroids[a]).total_cmp(&distance(*pixel, centroids[b]))
+                })
+                .unwrap_or(0);
+        }
+
+        let mut sums = vec![[0f32; 3]; centroids.len()];
+        let mut counts = vec![0usize; centroids.len()];
+        for (pixel, &assignment) in pixels.iter().zip(&assignments) {
+            for (sum, value) in sums[assignment].iter_mut().zip(pixel) {
+                *sum += value;
+            }
+            counts[assignment] += 1;
+        }
+        for (i, centroid) in centroids.iter_mut().enumerate() {
+            // An empty cluster keeps its previous centroid
+            if counts[i] > 0 {
+                *centroid = sums[i].map(|sum| sum / counts[i] as f32);
+            }
+        }
+    }
+
+    let mut counts = vec![0usize; centroids.len()];
+    for &assignment in &assignments {
+        counts[assignment] += 1;
+    }
+    let mut clusters: Vec<([f32; 3], usize)> = centroids
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    clusters.sort_by(|a, b| b.1.cmp(&a.1));
+    clusters
+}
+
+/// Compute the palette of an encoded image
+pub fn palette_from_bytes(bytes: &[u8]) -> Result<ThumbPalette, String> {
+    let image = image::load_from_memory(bytes)
+        .map_err(|e| tr_args("palette-invalid-image", &[("error", e.to_string().into())]))?;
+    let height = (SAMPLE_WIDTH * image.height() / image.width().max(1)).max(1);
+    let sample = image.thumbnail_exact(SAMPLE_WIDTH, height).to_rgb8();
+
+    let pixels: Vec<[f32; 3]> = sample
+        .pixels()
+        .map(|p| [p[0] as f32, p[1] as f32, p[2] as f32])
+        .collect();
+    if pixels.is_empty() {
+        return Err(tr("palette-no-pixels"));
+    }
+
+    let clusters = cluster(&pixels);
+    let total = pixels.len() as f32;
+    let colors: Vec<PaletteColor> = clusters
+        .iter()
+        .map(|(color, count)| PaletteColor {
+            hex: to_hex(*color),
+            weight: *count as f32 / total,
+        })
+        .collect();
+
+    let dominant = clusters[0].0;
+    let accent = clusters
+        .iter()
+        .filter(|(_, count)| *count as f32 / total >= MIN_ACCENT_WEIGHT)
+        .max_by(|a, b| vividness(a.0).total_cmp(&vividness(b.0)))
+        .map(|(color, _)| *color)
+        .unwrap_or(dominant);
+
+    Ok(ThumbPalette {
+        dominant: to_hex(dominant),
+        accent: to_hex(accent),
+        colors,
+    })
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn thumb_palette(
+    _app_handle: tauri::AppHandle,
+    video_id: String,
+) -> Result<ThumbPalette, String> {
+    // SECURITY: Validate input parameters
+    if !valid_video_id(&video_id) {
+        return Err(tr("palette-invalid-video-id"));
+    }
+
+    // Ensure thumbnail cache is initialized
+    let cache = get_thumb_cache().ok_or("Thumbnail cache not initialized")?;
+
+    // mqdefault exists for every video and has no letterboxing
+    let source_url = format!("https://i.ytimg.com/vi/{}/mqdefault.jpg", video_id);
+    let bytes = cache
+        .fetch(CacheKind::Thumbnails, &source_url)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    tauri::async_runtime::spawn_blocking(move || palette_from_bytes(&bytes))
+        .await
+        .map_err(|e| e.to_string())?
+}
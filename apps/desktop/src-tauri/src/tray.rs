@@ -0,0 +1,165 @@
+/// System Tray Module
+///
+/// Tray icon with quick actions for the main window, plus the close-to-tray and
+/// minimize-to-tray behaviors. Both behaviors are read from the settings store
+/// so the frontend settings page controls them directly.
+use tauri::menu::{Menu, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, Runtime, Window, WindowEvent};
+use tauri_plugin_store::StoreExt;
+
+/// Identifier of the app's tray icon
+pub const TRAY_ID: &str = "main";
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field: hide to tray instead of quitting on close
+const CLOSE_TO_TRAY_FIELD: &str = "close_to_tray";
+/// Settings store field: hide to tray when minimized
+const MINIMIZE_TO_TRAY_FIELD: &str = "minimize_to_tray";
+
+const MENU_TOGGLE_WINDOW: &str = "toggle_window";
+const MENU_QUIT: &str = "quit";
+
+/// Create the tray icon
+///
+/// # Arguments
+/// * `app` - App handle used to build the menu and icon
+///
+/// # Returns
+/// * `Ok(())` if the tray icon was created
+/// * `Err(tauri::Error)` if the platform has no tray or building failed
+pub fn init<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let menu = build_menu(app)?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("youtube.pub")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(|app, event| match event.id.as_ref() {
+            MENU_TOGGLE_WINDOW => toggle_main_window(app),
+            MENU_QUIT => app.exit(0),
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Rebuild the tray menu so its labels match the current window state
+pub fn refresh<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(tray) = app.tray_by_id(TRAY_ID) {
+        if let Ok(menu) = build_menu(app) {
+            let _ = tray.set_menu(Some(menu));
+        }
+    }
+}
+
+fn build_menu<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<Menu<R>> {
+    let visible = app
+        .get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(true);
+
+    let toggle_label = if visible {
+        crate::i18n::tr("tray-hide")
+    } else {
+        crate::i18n::tr("tray-show")
+    };
+
+    let toggle = MenuItem::with_id(app, MENU_TOGGLE_WINDOW, toggle_label, true, None::<&str>)?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let quit = MenuItem::with_id(
+        app,
+        MENU_QUIT,
+        crate::i18n::tr("tray-quit"),
+        true,
+        None::<&str>,
+    )?;
+
+    Menu::with_items(app, &[&toggle, &separator, &quit])
+}
+
+/// Show, unminimize and focus the main window
+pub fn show_main_window<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+    refresh(app);
+}
+
+/// Hide the main window to the tray
+pub fn hide_main_window<R: Runtime>(app: &AppHandle<R>) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    refresh(app);
+}
+
+fn toggle_main_window<R: Runtime>(app: &AppHandle<R>) {
+    let visible = app
+        .get_webview_window("main")
+        .and_then(|window| window.is_visible().ok())
+        .unwrap_or(false);
+
+    if visible {
+        hide_main_window(app);
+    } else {
+        show_main_window(app);
+    }
+}
+
+fn setting_enabled<R: Runtime>(app: &AppHandle<R>, field: &str) -> bool {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(field))
+        .and_then(|value| value.as_bool())
+        .unwrap_or(false)
+}
+
+/// Apply close-to-tray / minimize-to-tray to main window events
+///
+/// # Arguments
+/// * `window` - Window that received the event
+/// * `event` - The window event
+pub fn handle_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    if window.label() != "main" {
+        return;
+    }
+
+    let app = window.app_handle();
+    match event {
+        WindowEvent::CloseRequested { api, .. } => {
+            if setting_enabled(app, CLOSE_TO_TRAY_FIELD) && app.tray_by_id(TRAY_ID).is_some() {
+                api.prevent_close();
+                hide_main_window(app);
+            }
+        }
+        WindowEvent::Resized(_) => {
+            let minimized = window.is_minimized().unwrap_or(false);
+            if minimized
+                && setting_enabled(app, MINIMIZE_TO_TRAY_FIELD)
+                && app.tray_by_id(TRAY_ID).is_some()
+            {
+                hide_main_window(app);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,256 @@
+/// Updates Module
+///
+/// Wraps the Tauri updater with release channels, staged rollouts and
+/// deferred installs. Every downloaded package is checked against the
+/// minisign public key in `tauri.conf.json` before it is installed.
+///
+/// A release can be rolled out gradually by adding `"rollout": <0-100>` to its
+/// `latest.json`; each install has a stable random bucket and only sees the
+/// update once the rollout percentage covers it.
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Runtime};
+use tauri_plugin_store::StoreExt;
+use tauri_plugin_updater::{Update, UpdaterExt};
+
+/// Event emitted while an update downloads
+pub const UPDATE_PROGRESS_EVENT: &str = "update-download-progress";
+/// Event emitted when an update is staged to install on quit
+pub const UPDATE_READY_EVENT: &str = "update-ready";
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding the selected channel
+const UPDATE_CHANNEL_FIELD: &str = "update_channel";
+/// Settings store field holding this install's rollout bucket (0-99)
+const ROLLOUT_BUCKET_FIELD: &str = "update_rollout_bucket";
+
+const STABLE_ENDPOINT: &str =
+    "https://github.com/jiaweing/youtube.pub/releases/latest/download/latest.json";
+const BETA_ENDPOINT: &str =
+    "https://github.com/jiaweing/youtube.pub/releases/download/beta/latest.json";
+
+/// Release channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    Stable,
+    Beta,
+}
+
+impl UpdateChannel {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "stable" => Some(UpdateChannel::Stable),
+            "beta" => Some(UpdateChannel::Beta),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => "stable",
+            UpdateChannel::Beta => "beta",
+        }
+    }
+
+    fn endpoint(self) -> &'static str {
+        match self {
+            UpdateChannel::Stable => STABLE_ENDPOINT,
+            UpdateChannel::Beta => BETA_ENDPOINT,
+        }
+    }
+}
+
+/// When to install a downloaded update
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InstallMode {
+    /// Install and restart immediately
+    Now,
+    /// Install when the app exits
+    OnQuit,
+}
+
+/// Available update, as reported to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateInfo {
+    pub version: String,
+    pub current_version: String,
+    pub notes: Option<String>,
+    pub date: Option<String>,
+    pub channel: UpdateChannel,
+}
+
+/// Payload of `update-download-progress`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpdateProgress {
+    pub downloaded: u64,
+    pub total: Option<u64>,
+}
+
+/// Update found by the last check, and the package staged for install on quit
+struct UpdateState {
+    available: Option<Update>,
+    staged: Option<(Update, Vec<u8>)>,
+}
+
+static UPDATE_STATE: Mutex<UpdateState> = Mutex::new(UpdateState {
+    available: None,
+    staged: None,
+});
+
+/// Selected channel, defaulting to stable
+pub fn channel<R: Runtime>(app: &AppHandle<R>) -> UpdateChannel {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(UPDATE_CHANNEL_FIELD))
+        .and_then(|value| value.as_str().and_then(UpdateChannel::parse))
+        .unwrap_or(UpdateChannel::Stable)
+}
+
+/// This install's rollout bucket, assigned on first use
+fn rollout_bucket<R: Runtime>(app: &AppHandle<R>) -> u64 {
+    let Ok(store) = app.store(SETTINGS_STORE_NAME) else {
+        return 0;
+    };
+    if let Some(bucket) = store.get(ROLLOUT_BUCKET_FIELD).and_then(|v| v.as_u64()) {
+        return bucket;
+    }
+
+    let bucket = rand::thread_rng().gen_range(0..100u64);
+    store.set(ROLLOUT_BUCKET_FIELD, bucket);
+    let _ = store.save();
+    bucket
+}
+
+/// Whether the release's rollout percentage covers this install
+fn in_rollout<R: Runtime>(app: &AppHandle<R>, update: &Update) -> bool {
+    match update.raw_json.get("rollout").and_then(|v| v.as_u64()) {
+        Some(percentage) => rollout_bucket(app) < percentage.min(100),
+        None => true,
+    }
+}
+
+/// Check the selected channel for an update
+///
+/// # Returns
+/// * `Ok(Some(UpdateInfo))` if a newer version is available to this install
+/// * `Ok(None)` if up to date
+/// * `Err(String)` if the check failed
+pub async fn check<R: Runtime>(app: &AppHandle<R>) -> Result<Option<UpdateInfo>, String> {
+    let channel = channel(app);
+    let endpoint = channel
+        .endpoint()
+        .parse()
+        .map_err(|e: url::ParseError| e.to_string())?;
+
+    let update = app
+        .updater_builder()
+        .endpoints(vec![endpoint])
+        .map_err(|e| e.to_string())?
+        .build()
+        .map_err(|e| e.to_string())?
+        .check()
+        .await
+        .map_err(|e| e.to_string())?
+        .filter(|update| in_rollout(app, update));
+
+    let info = update.as_ref().map(|update| UpdateInfo {
+        version: update.version.clone(),
+        current_version: update.current_version.clone(),
+        notes: update.body.clone(),
+        date: update.date.map(|date| date.to_string()),
+        channel,
+    });
+
+    UPDATE_STATE.lock().unwrap().available = update;
+    Ok(info)
+}
+
+/// Download the update found by `check` and install it
+///
+/// The signature is verified during download. With `InstallMode::Now` the app
+/// restarts into the new version; with `InstallMode::OnQuit` the package is
+/// kept until exit and `update-ready` is emitted.
+pub async fn install<R: Runtime>(app: &AppHandle<R>, mode: InstallMode) -> Result<(), String> {
+    let update = UPDATE_STATE
+        .lock()
+        .unwrap()
+        .available
+        .clone()
+        .ok_or_else(|| crate::i18n::tr("update-none-available"))?;
+
+    let progress_handle = app.clone();
+    let mut downloaded = 0u64;
+    let bytes = update
+        .download(
+            move |chunk_length, total| {
+                downloaded += chunk_length as u64;
+                let _ = progress_handle
+                    .emit(UPDATE_PROGRESS_EVENT, UpdateProgress { downloaded, total });
+            },
+            || {},
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match mode {
+        InstallMode::Now => {
+            update.install(bytes).map_err(|e| e.to_string())?;
+            app.restart();
+        }
+        InstallMode::OnQuit => {
+            let version = update.version.clone();
+            UPDATE_STATE.lock().unwrap().staged = Some((update, bytes));
+            let _ = app.emit(UPDATE_READY_EVENT, version);
+            Ok(())
+        }
+    }
+}
+
+/// Install a package staged with `InstallMode::OnQuit`, e.g. on exit
+pub fn install_staged() {
+    let staged = UPDATE_STATE.lock().unwrap().staged.take();
+    if let Some((update, bytes)) = staged {
+        if let Err(e) = update.install(bytes) {
+            tracing::error!("Failed to install staged update: {}", e);
+        }
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn update_check(app_handle: tauri::AppHandle) -> Result<Option<UpdateInfo>, String> {
+    check(&app_handle).await
+}
+
+#[tauri::command]
+pub async fn update_install(app_handle: tauri::AppHandle, mode: InstallMode) -> Result<(), String> {
+    install(&app_handle, mode).await
+}
+
+#[tauri::command]
+pub async fn update_get_channel(app_handle: tauri::AppHandle) -> Result<UpdateChannel, String> {
+    Ok(channel(&app_handle))
+}
+
+#[tauri::command]
+pub async fn update_set_channel(
+    app_handle: tauri::AppHandle,
+    channel: UpdateChannel,
+) -> Result<(), String> {
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    store.set(UPDATE_CHANNEL_FIELD, channel.as_str());
+    store.save().map_err(|e| e.to_string())?;
+
+    // A check against the old channel is no longer relevant
+    UPDATE_STATE.lock().unwrap().available = None;
+    Ok(())
+}
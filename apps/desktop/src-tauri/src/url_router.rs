@@ -0,0 +1,379 @@
+/// URL Router Module
+///
+/// Parses `ytpub://` deep links and youtube.com / youtu.be URLs into typed
+/// navigation targets and emits them to the frontend as `navigate` events.
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter, Runtime};
+
+/// Event emitted when a link should be opened in the app
+pub const NAVIGATE_EVENT: &str = "navigate";
+
+/// Custom URL scheme registered for the app
+pub const APP_SCHEME: &str = "ytpub";
+
+/// Hosts recognized as YouTube web links
+const YOUTUBE_HOSTS: &[&str] = &[
+    "youtube.com",
+    "www.youtube.com",
+    "m.youtube.com",
+    "music.youtube.com",
+    "youtube-nocookie.com",
+    "www.youtube-nocookie.com",
+];
+
+/// Where a link points
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum NavigateTarget {
+    #[serde(rename_all = "camelCase")]
+    Video {
+        video_id: String,
+        /// Start position in seconds
+        start_seconds: Option<u64>,
+        /// Playlist the video was opened from
+        playlist_id: Option<String>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Playlist { playlist_id: String },
+    #[serde(rename_all = "camelCase")]
+    Channel {
+        /// `UC…` channel ID, when the link contains one
+        channel_id: Option<String>,
+        /// `@handle`, legacy `/c/` or `/user/` name otherwise
+        handle: Option<String>,
+    },
+}
+
+/// Parse any supported link into a navigation target
+///
+/// # Arguments
+/// * `input` - A `ytpub://` link or a YouTube web URL
+///
+/// # Returns
+/// * `Some(NavigateTarget)` if the link is recognized
+/// * `None` otherwise
+pub fn parse(input: &str) -> Option<NavigateTarget> {
+    let input = input.trim();
+    if input.len() > crate::security::MAX_URL_LENGTH {
+        return None;
+    }
+
+    // Accept bare "youtube.com/..." / "youtu.be/..." as typed or copied
+    let url = match url::Url::parse(input) {
+        Ok(url) => url,
+        Err(_) => url::Url::parse(&format!("https://{}", input)).ok()?,
+    };
+
+    match url.scheme() {
+        APP_SCHEME => parse_app_link(&url),
+        "http" | "https" => parse_web_link(&url),
+        _ => None,
+    }
+}
+
+/// Parse `ytpub://` links
+///
+/// Supported forms: `ytpub://watch?v=ID&t=90`, `ytpub://video/ID`,
+/// `ytpub://playlist/ID`, `ytpub://channel/ID`, `ytpub://open?url=<web URL>`.
+fn parse_app_link(url: &url::Url) -> Option<NavigateTarget> {
+    let route = url.host_str().unwrap_or_default();
+    let first_segment = url
+        .path_segments()
+        .and_then(|mut segments| segments.next())
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_string());
+
+    match route {
+        "open" => parse_web_link(&url::Url::parse(&query_param(url, "url")?).ok()?),
+        "watch" => video_target(url, query_param(url, "v")?),
+        "video" => video_target(url, first_segment?),
+        "playlist" => Some(NavigateTarget::Playlist {
+            playlist_id: valid_id(first_segment.or_else(|| query_param(url, "list"))?)?,
+        }),
+        "channel" => channel_target(first_segment?),
+        _ => None,
+    }
+}
+
+/// Parse youtube.com and youtu.be links
+fn parse_web_link(url: &url::Url) -> Option<NavigateTarget> {
+    let host = url.host_str()?.to_ascii_lowercase();
+    let segments: Vec<&str> = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+
+    if host == "youtu.be" || host == "www.youtu.be" {
+        return video_target(url, segments.first()?.to_string());
+    }
+
+    if !YOUTUBE_HOSTS.contains(&host.as_str()) {
+        return None;
+    }
+
+    match segments.as_slice() {
+        ["watch"] => video_target(url, query_param(url, "v")?),
+        ["shorts", id] | ["live", id] | ["embed", id] | ["v", id] => {
+            video_target(url, id.to_string())
+        }
+        ["playlist"] => Some(NavigateTarget::Playlist {
+            playlist_id: valid_id(query_param(url, "list")?)?,
+        }),
+        ["channel", id, ..] => channel_target(id.to_string()),
+        ["c", name, ..] | ["user", name, ..] => Some(NavigateTarget::Channel {
+            channel_id: None,
+            handle: Some(valid_id(name.to_string())?),
+        }),
+        [handle, ..] if handle.starts_with('@') => channel_target(handle.to_string()),
+        _ => None,
+    }
+}
+
+fn video_target(url: &url::Url, video_id: String) -> Option<NavigateTarget> {
+    let video_id = valid_id(video_id)?;
+    if video_id.len() != 11 {
+        return None;
+    }
+
+    let start_seconds = query_param(url, "t")
+        .or_else(|| query_param(url, "start"))
+        .or_else(|| {
+            // youtu.be and share links sometimes carry the timestamp as #t=
+            url.fragment()
+                .and_then(|fragment| fragment.strip_prefix("t="))
+                .map(|t| t.to_string())
+        })
+        .and_then(|t| parse_timestamp(&t));
+
+    Some(NavigateTarget::Video {
+        video_id,
+        start_seconds,
+        playlist_id: query_param(url, "list").and_then(valid_id),
+    })
+}
+
+fn channel_target(id: String) -> Option<NavigateTarget> {
+    if let Some(handle) = id.strip_prefix('@') {
+        return Some(NavigateTarget::Channel {
+            channel_id: None,
+            handle: Some(format!("@{}", valid_id(handle.to_string())?)),
+        });
+    }
+
+    let id = valid_id(id)?;
+    if id.starts_with("UC") {
+        Some(NavigateTarget::Channel {
+            channel_id: Some(id),
+            handle: None,
+        })
+    } else {
+        Some(NavigateTarget::Channel {
+            channel_id: None,
+            handle: Some(id),
+        })
+    }
+}
+
+/// Parse `90`, `90s`, `1m30s` or `1h2m3s` into seconds
+///
+/// Returns `None` for anything malformed, including values that overflow.
+pub fn parse_timestamp(value: &str) -> Option<u64> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(seconds);
+    }
+
+    let mut total = 0u64;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let amount: u64 = number.parse().ok()?;
+        number.clear();
+        let seconds = match c {
+            'h' => amount.checked_mul(3600)?,
+            'm' => amount.checked_mul(60)?,
+            's' => amount,
+            _ => return None,
+        };
+        total = total.checked_add(seconds)?;
+    }
+
+    if !number.is_empty() {
+        return None;
+    }
+    Some(total)
+}
+
+fn query_param(url: &url::Url, name: &str) -> Option<String> {
+    url.query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+        .filter(|value| !value.is_empty())
+}
+
+/// Accept only the character set YouTube uses for IDs and handles
+fn valid_id(id: String) -> Option<String> {
+    let valid = !id.is_empty()
+        && id.len() <= 128
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if valid {
+        Some(id)
+    } else {
+        None
+    }
+}
+
+/// Parse a link and emit a `navigate` event for it
+///
+/// # Arguments
+/// * `app` - App handle used to emit the event
+/// * `input` - Link to route
+///
+/// # Returns
+/// * `true` if the link was recognized and emitted
+pub fn route<R: Runtime>(app: &AppHandle<R>, input: &str) -> bool {
+    match parse(input) {
+        Some(target) => {
+            crate::tray::show_main_window(app);
+            app.emit(NAVIGATE_EVENT, target).is_ok()
+        }
+        None => false,
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn url_parse(
+    _app_handle: tauri::AppHandle,
+    url: String,
+) -> Result<Option<NavigateTarget>, String> {
+    // SECURITY: Validate input parameters
+    crate::security::validate_user_input(&url, "URL", crate::security::MAX_URL_LENGTH)
+        .map_err(|e| format!("Invalid URL: {}", e))?;
+
+    Ok(parse(&url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VIDEO_ID: &str = "dQw4w9WgXcQ";
+
+    fn video(start_seconds: Option<u64>, playlist_id: Option<&str>) -> Option<NavigateTarget> {
+        Some(NavigateTarget::Video {
+            video_id: VIDEO_ID.to_string(),
+            start_seconds,
+            playlist_id: playlist_id.map(String::from),
+        })
+    }
+
+    #[test]
+    fn parses_web_video_links() {
+        assert_eq!(
+            parse("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=1m30s"),
+            video(Some(90), None)
+        );
+        assert_eq!(
+            parse("https://m.youtube.com/watch?v=dQw4w9WgXcQ&list=PLabc"),
+            video(None, Some("PLabc"))
+        );
+        assert_eq!(
+            parse("https://www.youtube.com/shorts/dQw4w9WgXcQ"),
+            video(None, None)
+        );
+        assert_eq!(
+            parse("https://youtu.be/dQw4w9WgXcQ#t=15s"),
+            video(Some(15), None)
+        );
+        assert_eq!(parse("youtu.be/dQw4w9WgXcQ?t=42"), video(Some(42), None));
+    }
+
+    #[test]
+    fn parses_app_links() {
+        assert_eq!(
+            parse("ytpub://watch?v=dQw4w9WgXcQ&t=90"),
+            video(Some(90), None)
+        );
+        assert_eq!(parse("ytpub://video/dQw4w9WgXcQ"), video(None, None));
+        assert_eq!(
+            parse("ytpub://open?url=https%3A%2F%2Fyoutu.be%2FdQw4w9WgXcQ"),
+            video(None, None)
+        );
+        assert_eq!(
+            parse("ytpub://playlist/PLabc"),
+            Some(NavigateTarget::Playlist {
+                playlist_id: "PLabc".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn parses_channel_links() {
+        assert_eq!(
+            parse("https://www.youtube.com/channel/UCabc123"),
+            Some(NavigateTarget::Channel {
+                channel_id: Some("UCabc123".to_string()),
+                handle: None,
+            })
+        );
+        assert_eq!(
+            parse("https://www.youtube.com/@someone"),
+            Some(NavigateTarget::Channel {
+                channel_id: None,
+                handle: Some("@someone".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unrecognized_links() {
+        assert_eq!(parse("https://example.com/watch?v=dQw4w9WgXcQ"), None);
+        assert_eq!(parse("https://www.youtube.com/watch?v=short"), None);
+        assert_eq!(parse("https://www.youtube.com/watch?v=dQw4w9WgXc<"), None);
+        assert_eq!(parse("ytpub://unknown/dQw4w9WgXcQ"), None);
+        assert_eq!(parse("ytpub://playlist/PL<bad"), None);
+        assert_eq!(parse("ytpub://playlist?list=PL%22%3E"), None);
+        assert_eq!(parse("ftp://www.youtube.com/watch?v=dQw4w9WgXcQ"), None);
+    }
+
+    #[test]
+    fn ignores_malformed_timestamps() {
+        assert_eq!(
+            parse("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=99999999999999999h"),
+            video(None, None)
+        );
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        assert_eq!(parse_timestamp("90"), Some(90));
+        assert_eq!(parse_timestamp("90s"), Some(90));
+        assert_eq!(parse_timestamp("1m30s"), Some(90));
+        assert_eq!(parse_timestamp("1h2m3s"), Some(3723));
+        assert_eq!(parse_timestamp(" 2m "), Some(120));
+    }
+
+    #[test]
+    fn rejects_malformed_timestamps() {
+        assert_eq!(parse_timestamp("1x"), None);
+        assert_eq!(parse_timestamp("12m3"), None);
+        assert_eq!(parse_timestamp("h"), None);
+        assert_eq!(parse_timestamp("-5"), None);
+    }
+
+    #[test]
+    fn rejects_overflowing_timestamps() {
+        assert_eq!(parse_timestamp("99999999999999999h"), None);
+        assert_eq!(parse_timestamp("999999999999999999m"), None);
+        assert_eq!(parse_timestamp("18446744073709551615s1s"), None);
+        assert_eq!(parse_timestamp("18446744073709551615"), Some(u64::MAX));
+    }
+}
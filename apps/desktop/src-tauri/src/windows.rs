@@ -0,0 +1,661 @@
+/// Window Management Module
+///
+/// Owns window setup and persists each window's size, position, maximized and
+/// fullscreen state (plus the monitor it was on) keyed by window label. Saved
+/// geometry is checked against the connected monitors on restore so windows
+/// never reappear on a display that has been unplugged. The webview zoom
+/// factor is persisted per label as well.
+///
+/// Compact mode shrinks the main window to its own remembered geometry and
+/// hides the overlay titlebar; the normal geometry is restored on exit.
+///
+/// Titlebar metrics (scale factor, maximized/fullscreen) are pushed to the
+/// frontend when they change so the custom titlebar can follow DPI changes
+/// between monitors. Snap Layouts on the maximize button come from decorum's
+/// snap overlay.
+///
+/// Fullscreen can target a chosen monitor; the preferred one is remembered and
+/// falls back to the window's current monitor when it's unplugged.
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use tauri::webview::{PageLoadEvent, PageLoadPayload};
+use tauri::{
+    AppHandle, Emitter, LogicalSize, Manager, Monitor, PhysicalPosition, PhysicalSize, Runtime,
+    Webview, WebviewWindow, WebviewWindowBuilder, Window, WindowEvent,
+};
+use tauri_plugin_decorum::WebviewWindowExt;
+use tauri_plugin_store::StoreExt;
+
+/// Store holding persisted window state
+const WINDOW_STATE_STORE_NAME: &str = "window-state.json";
+
+/// Window state store key for the main window's compact geometry
+const COMPACT_STATE_KEY: &str = "main:compact";
+
+/// Id of the stylesheet hiding the titlebar in compact mode
+const COMPACT_STYLE_ID: &str = "ytpub-compact-mode";
+/// Event emitted when compact mode is toggled
+pub const COMPACT_MODE_EVENT: &str = "compact-mode-changed";
+
+/// Event emitted when a window's titlebar metrics change
+pub const TITLEBAR_METRICS_EVENT: &str = "titlebar-metrics-changed";
+
+/// Compact size used until the user has resized the compact window
+const DEFAULT_COMPACT_SIZE: (f64, f64) = (480.0, 300.0);
+/// Smallest size the compact window can be resized to
+const COMPACT_MIN_SIZE: (f64, f64) = (320.0, 200.0);
+
+/// macOS traffic-light position within the overlay titlebar
+#[cfg(target_os = "macos")]
+const TRAFFIC_LIGHTS_INSET: (f32, f32) = (12.0, 16.0);
+
+/// Settings store shared with the frontend
+const SETTINGS_STORE_NAME: &str = "settings.json";
+/// Settings store field holding zoom factors keyed by window label
+const WINDOW_ZOOM_FIELD: &str = "window_zoom";
+
+/// Settings store field holding the preferred fullscreen monitor name
+const PREFERRED_MONITOR_FIELD: &str = "preferred_fullscreen_monitor";
+
+/// Accepted zoom factor range
+const MIN_ZOOM: f64 = 0.5;
+const MAX_ZOOM: f64 = 3.0;
+
+/// Smallest visible overlap (in physical pixels) for saved geometry to count as on-screen
+const MIN_VISIBLE_PX: i32 = 64;
+
+/// Persisted state of a single window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowState {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub fullscreen: bool,
+    /// Name of the monitor the window was on, if known
+    pub monitor: Option<String>,
+}
+
+/// State the custom titlebar lays itself out from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TitlebarMetrics {
+    pub label: String,
+    pub scale_factor: f64,
+    pub maximized: bool,
+    pub fullscreen: bool,
+}
+
+/// Last metrics emitted per window label
+static LAST_TITLEBAR_METRICS: Mutex<BTreeMap<String, TitlebarMetrics>> =
+    Mutex::new(BTreeMap::new());
+
+/// A connected monitor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub primary: bool,
+}
+
+/// Whether the main window is in compact mode
+static COMPACT: AtomicBool = AtomicBool::new(false);
+
+/// Main window state to return to when compact mode ends
+static NORMAL_STATE: Mutex<Option<WindowState>> = Mutex::new(None);
+
+/// Create the main window and restore its saved state
+///
+/// The main window is declared in `tauri.conf.json` but created here, so its
+/// webview can use the active profile's storage. It starts hidden and is shown
+/// once its geometry has been restored, to avoid a visible jump on launch. A
+/// background launch at login leaves it hidden.
+pub fn setup_main_window<R: Runtime>(app: &AppHandle<R>) -> tauri::Result<()> {
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|window| window.label == "main")
+        .cloned()
+        .expect("main window missing from tauri.conf.json");
+    let builder = WebviewWindowBuilder::from_config(app, &config)?;
+    #[cfg(not(target_os = "macos"))]
+    let builder = match crate::profiles::webview_data_dir(app) {
+        Some(dir) => builder.data_directory(dir),
+        None => builder,
+    };
+    #[cfg(target_os = "macos")]
+    let builder = match crate::profiles::webview_data_store_id(app) {
+        Some(id) => builder.data_store_identifier(id),
+        None => builder,
+    };
+    let main_window = builder.build()?;
+    main_window.create_overlay_titlebar().unwrap();
+
+    #[cfg(target_os = "macos")]
+    {
+        main_window
+            .set_traffic_lights_inset(TRAFFIC_LIGHTS_INSET.0, TRAFFIC_LIGHTS_INSET.1)
+            .unwrap();
+    }
+
+    restore_state(&main_window);
+    apply_zoom(&main_window);
+    crate::theme::apply_to_window(&main_window);
+
+    // Launched at login in background mode: stay in the tray until requested
+    if !crate::autostart::start_in_background(app) {
+        main_window.show()?;
+    }
+
+    Ok(())
+}
+
+/// Restore a window's saved state, if any
+///
+/// # Arguments
+/// * `window` - Window to restore; its label is the persistence key
+pub fn restore_state<R: Runtime>(window: &WebviewWindow<R>) {
+    let Some(state) = load_state(window.app_handle(), window.label()) else {
+        return;
+    };
+    apply_state(window, &state);
+}
+
+/// Move and resize a window to a saved state, keeping it on a connected monitor
+fn apply_state<R: Runtime>(window: &WebviewWindow<R>, state: &WindowState) {
+    let monitors = window.available_monitors().unwrap_or_default();
+    match find_target_monitor(&monitors, state) {
+        Some(_) => {
+            let _ = window.set_size(PhysicalSize::new(state.width, state.height));
+            let _ = window.set_position(PhysicalPosition::new(state.x, state.y));
+        }
+        None => {
+            // The saved monitor is gone: keep the size if it fits the primary
+            // monitor and center the window there instead
+            let primary = window.primary_monitor().ok().flatten();
+            let (width, height) = match &primary {
+                Some(monitor) => (
+                    state.width.min(monitor.size().width),
+                    state.height.min(monitor.size().height),
+                ),
+                None => (state.width, state.height),
+            };
+            let _ = window.set_size(PhysicalSize::new(width, height));
+            let _ = window.center();
+        }
+    }
+
+    if state.maximized {
+        let _ = window.maximize();
+    }
+    if state.fullscreen {
+        let _ = window.set_fullscreen(true);
+    }
+}
+
+/// Find the monitor the saved geometry is sufficiently visible on
+fn find_target_monitor<'a>(monitors: &'a [Monitor], state: &WindowState) -> Option<&'a Monitor> {
+    let visible_on = |monitor: &Monitor| {
+        let position = monitor.position();
+        let size = monitor.size();
+
+        let left = state.x.max(position.x);
+        let top = state.y.max(position.y);
+        let right = (state.x + state.width as i32).min(position.x + size.width as i32);
+        let bottom = (state.y + state.height as i32).min(position.y + size.height as i32);
+
+        right - left >= MIN_VISIBLE_PX && bottom - top >= MIN_VISIBLE_PX
+    };
+
+    // Prefer the monitor the window was on, if it's still connected
+    if let Some(name) = &state.monitor {
+        if let Some(monitor) = monitors
+            .iter()
+            .find(|m| m.name() == Some(name) && visible_on(m))
+        {
+            return Some(monitor);
+        }
+    }
+
+    monitors.iter().find(|m| visible_on(m))
+}
+
+/// Capture a window's current state
+fn capture_state<R: Runtime>(window: &Window<R>) -> Option<WindowState> {
+    let maximized = window.is_maximized().unwrap_or(false);
+    let fullscreen = window.is_fullscreen().unwrap_or(false);
+    let minimized = window.is_minimized().unwrap_or(false);
+
+    // Geometry of a minimized window is meaningless on some platforms
+    if minimized {
+        return None;
+    }
+
+    let position = window.outer_position().ok()?;
+    let size = window.inner_size().ok()?;
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+
+    Some(WindowState {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized,
+        fullscreen,
+        monitor,
+    })
+}
+
+/// Load the saved state for a window label
+pub fn load_state<R: Runtime>(app: &AppHandle<R>, label: &str) -> Option<WindowState> {
+    app.store(WINDOW_STATE_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(label))
+        .and_then(|value| serde_json::from_value(value).ok())
+}
+
+/// Whether the main window is in compact mode
+pub fn is_compact() -> bool {
+    COMPACT.load(Ordering::SeqCst)
+}
+
+/// Minimum size of the main window from `tauri.conf.json`
+fn configured_min_size<R: Runtime>(app: &AppHandle<R>) -> Option<LogicalSize<f64>> {
+    let config = app
+        .config()
+        .app
+        .windows
+        .iter()
+        .find(|w| w.label == "main")?;
+    Some(LogicalSize::new(config.min_width?, config.min_height?))
+}
+
+/// Show or hide the decorum overlay titlebar
+///
+/// Hides it with a stylesheet rather than on the elements so it also covers a
+/// titlebar decorum creates after this runs.
+fn set_titlebar_visible<R: Runtime>(webview: &Webview<R>, visible: bool) {
+    let script = if visible {
+        format!("document.getElementById('{}')?.remove();", COMPACT_STYLE_ID)
+    } else {
+        format!(
+            "if (!document.getElementById('{id}')) {{ const style = document.createElement('style'); style.id = '{id}'; style.textContent = '[data-tauri-decorum-tb] {{ display: none !important; }}'; document.head.appendChild(style); }}",
+            id = COMPACT_STYLE_ID
+        )
+    };
+    let _ = webview.eval(&script);
+}
+
+/// Hide the titlebar again after the main window reloads in compact mode
+pub fn handle_page_load<R: Runtime>(webview: &Webview<R>, payload: &PageLoadPayload<'_>) {
+    if matches!(payload.event(), PageLoadEvent::Finished)
+        && webview.label() == "main"
+        && is_compact()
+    {
+        set_titlebar_visible(webview, false);
+    }
+}
+
+/// Enter or leave compact mode on the main window
+///
+/// Entering remembers the current geometry, leaves maximized/fullscreen, and
+/// applies the saved compact geometry (or a small default). Leaving restores
+/// the remembered geometry and the configured minimum size.
+pub fn set_compact_mode<R: Runtime>(app: &AppHandle<R>, enabled: bool) -> tauri::Result<()> {
+    let Some(window) = app.get_webview_window("main") else {
+        return Ok(());
+    };
+    if enabled == is_compact() {
+        return Ok(());
+    }
+
+    if enabled {
+        window.unminimize()?;
+        let normal = capture_state(&window.as_ref().window()).or_else(|| load_state(app, "main"));
+        *NORMAL_STATE.lock().unwrap() = normal;
+
+        if window.is_fullscreen()? {
+            window.set_fullscreen(false)?;
+        }
+        if window.is_maximized()? {
+            window.unmaximize()?;
+        }
+
+        // Set before resizing so the resize is saved as compact geometry
+        COMPACT.store(true, Ordering::SeqCst);
+        window.set_min_size(Some(LogicalSize::new(
+            COMPACT_MIN_SIZE.0,
+            COMPACT_MIN_SIZE.1,
+        )))?;
+        match load_state(app, COMPACT_STATE_KEY) {
+            Some(state) => apply_state(&window, &state),
+            None => window.set_size(LogicalSize::new(
+                DEFAULT_COMPACT_SIZE.0,
+                DEFAULT_COMPACT_SIZE.1,
+            ))?,
+        }
+        set_titlebar_visible(window.as_ref(), false);
+    } else {
+        COMPACT.store(false, Ordering::SeqCst);
+        window.set_min_size(configured_min_size(app))?;
+        if let Some(state) = NORMAL_STATE.lock().unwrap().take() {
+            apply_state(&window, &state);
+        }
+        set_titlebar_visible(window.as_ref(), true);
+    }
+
+    // macOS resets the traffic-light position when the window is resized
+    #[cfg(target_os = "macos")]
+    {
+        let _ = window.set_traffic_lights_inset(TRAFFIC_LIGHTS_INSET.0, TRAFFIC_LIGHTS_INSET.1);
+    }
+
+    let _ = app.emit(COMPACT_MODE_EVENT, enabled);
+    Ok(())
+}
+
+/// Store key for a window's geometry; the compact main window has its own
+fn state_key<R: Runtime>(window: &Window<R>) -> &str {
+    if window.label() == "main" && is_compact() {
+        COMPACT_STATE_KEY
+    } else {
+        window.label()
+    }
+}
+
+/// Save a window's current state
+pub fn save_state<R: Runtime>(window: &Window<R>) {
+    let app = window.app_handle();
+    let key = state_key(window);
+    let Ok(store) = app.store(WINDOW_STATE_STORE_NAME) else {
+        return;
+    };
+
+    let Some(mut state) = capture_state(window) else {
+        return;
+    };
+
+    // Keep the restored (non-maximized) geometry so un-maximizing after
+    // relaunch returns to the previous size
+    if state.maximized || state.fullscreen {
+        if let Some(previous) = load_state(app, key) {
+            state.x = previous.x;
+            state.y = previous.y;
+            state.width = previous.width;
+            state.height = previous.height;
+        }
+    }
+
+    if let Ok(value) = serde_json::to_value(&state) {
+        store.set(key, value);
+    }
+}
+
+/// Persist window state on geometry changes and before closing
+///
+/// # Arguments
+/// * `window` - Window that received the event
+/// * `event` - The window event
+pub fn handle_window_event<R: Runtime>(window: &Window<R>, event: &WindowEvent) {
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+            save_state(window);
+            emit_titlebar_metrics(window);
+        }
+        WindowEvent::ScaleFactorChanged { .. } => emit_titlebar_metrics(window),
+        WindowEvent::CloseRequested { .. } => {
+            save_state(window);
+            if let Ok(store) = window.app_handle().store(WINDOW_STATE_STORE_NAME) {
+                let _ = store.save();
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Current titlebar metrics of a window
+pub fn titlebar_metrics<R: Runtime>(window: &Window<R>) -> TitlebarMetrics {
+    TitlebarMetrics {
+        label: window.label().to_string(),
+        scale_factor: window.scale_factor().unwrap_or(1.0),
+        maximized: window.is_maximized().unwrap_or(false),
+        fullscreen: window.is_fullscreen().unwrap_or(false),
+    }
+}
+
+/// Emit `titlebar-metrics-changed` if a window's metrics differ from the last emitted
+fn emit_titlebar_metrics<R: Runtime>(window: &Window<R>) {
+    let metrics = titlebar_metrics(window);
+    {
+        let mut last = LAST_TITLEBAR_METRICS.lock().unwrap();
+        if last.get(window.label()) == Some(&metrics) {
+            return;
+        }
+        last.insert(window.label().to_string(), metrics.clone());
+    }
+    let _ = window.emit(TITLEBAR_METRICS_EVENT, metrics);
+}
+
+/// Connected monitors
+pub fn monitors<R: Runtime>(app: &AppHandle<R>) -> Vec<MonitorInfo> {
+    let primary = app
+        .primary_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|monitor| MonitorInfo {
+            name: monitor.name().cloned(),
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+            scale_factor: monitor.scale_factor(),
+            primary: primary.is_some() && monitor.name() == primary.as_ref(),
+        })
+        .collect()
+}
+
+/// Name of the preferred fullscreen monitor, if one was chosen
+pub fn preferred_monitor<R: Runtime>(app: &AppHandle<R>) -> Option<String> {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(PREFERRED_MONITOR_FIELD))
+        .and_then(|value| value.as_str().map(|name| name.to_string()))
+}
+
+/// Enter fullscreen on a monitor
+///
+/// # Arguments
+/// * `window` - Window to make fullscreen
+/// * `monitor` - Monitor name; `None` uses the preferred monitor. A monitor
+///   that isn't connected falls back to the window's current one.
+pub fn fullscreen_on_monitor<R: Runtime>(
+    window: &WebviewWindow<R>,
+    monitor: Option<String>,
+) -> tauri::Result<()> {
+    let name = monitor.or_else(|| preferred_monitor(window.app_handle()));
+    let target = name.and_then(|name| {
+        window
+            .available_monitors()
+            .unwrap_or_default()
+            .into_iter()
+            .find(|m| m.name() == Some(&name))
+    });
+
+    if let Some(target) = target {
+        if window.is_fullscreen()? {
+            window.set_fullscreen(false)?;
+        }
+        if window.is_maximized()? {
+            window.unmaximize()?;
+        }
+        // Fullscreen uses the monitor the window is on, so move it there first
+        let position = target.position();
+        window.set_position(PhysicalPosition::new(position.x, position.y))?;
+    }
+
+    window.set_fullscreen(true)
+}
+
+/// Saved zoom factor for a window label, defaulting to 1.0
+pub fn load_zoom<R: Runtime>(app: &AppHandle<R>, label: &str) -> f64 {
+    app.store(SETTINGS_STORE_NAME)
+        .ok()
+        .and_then(|store| store.get(WINDOW_ZOOM_FIELD))
+        .and_then(|value| value.get(label).and_then(|zoom| zoom.as_f64()))
+        .map(|zoom| zoom.clamp(MIN_ZOOM, MAX_ZOOM))
+        .unwrap_or(1.0)
+}
+
+/// Apply a window's saved zoom factor
+pub fn apply_zoom<R: Runtime>(window: &WebviewWindow<R>) {
+    let zoom = load_zoom(window.app_handle(), window.label());
+    if zoom != 1.0 {
+        let _ = window.set_zoom(zoom);
+    }
+}
+
+// Tauri commands for frontend integration
+
+#[tauri::command]
+pub async fn zoom_set(
+    app_handle: tauri::AppHandle,
+    label: String,
+    zoom: f64,
+) -> Result<(), String> {
+    let window = app_handle
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Unknown window: {}", label))?;
+    // SECURITY: Validate input parameters
+    if !(MIN_ZOOM..=MAX_ZOOM).contains(&zoom) {
+        return Err(format!(
+            "Zoom must be between {} and {}",
+            MIN_ZOOM, MAX_ZOOM
+        ));
+    }
+
+    window.set_zoom(zoom).map_err(|e| e.to_string())?;
+
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    let mut levels = store
+        .get(WINDOW_ZOOM_FIELD)
+        .filter(|value| value.is_object())
+        .unwrap_or_else(|| serde_json::json!({}));
+    levels[label.as_str()] = serde_json::json!(zoom);
+    store.set(WINDOW_ZOOM_FIELD, levels);
+    store.save().map_err(|e| e.to_string())?;
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn zoom_get(app_handle: tauri::AppHandle, label: String) -> Result<f64, String> {
+    Ok(load_zoom(&app_handle, &label))
+}
+
+#[tauri::command]
+pub async fn window_set_always_on_top(
+    app_handle: tauri::AppHandle,
+    label: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let window = app_handle
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Unknown window: {}", label))?;
+
+    window.set_always_on_top(enabled).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn window_compact_mode(
+    app_handle: tauri::AppHandle,
+    enabled: bool,
+) -> Result<(), String> {
+    set_compact_mode(&app_handle, enabled).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn window_titlebar_metrics(
+    app_handle: tauri::AppHandle,
+    label: String,
+) -> Result<TitlebarMetrics, String> {
+    let window = app_handle
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Unknown window: {}", label))?;
+
+    Ok(titlebar_metrics(&window.as_ref().window()))
+}
+
+#[tauri::command]
+pub async fn monitors_list(app_handle: tauri::AppHandle) -> Result<Vec<MonitorInfo>, String> {
+    Ok(monitors(&app_handle))
+}
+
+#[tauri::command]
+pub async fn window_fullscreen_on_monitor(
+    app_handle: tauri::AppHandle,
+    label: String,
+    monitor: Option<String>,
+) -> Result<(), String> {
+    let window = app_handle
+        .get_webview_window(&label)
+        .ok_or_else(|| format!("Unknown window: {}", label))?;
+    // SECURITY: Validate input parameters
+    if let Some(monitor) = &monitor {
+        crate::security::validate_user_input(monitor, "monitor", 256)
+            .map_err(|e| format!("Invalid monitor: {}", e))?;
+    }
+
+    fullscreen_on_monitor(&window, monitor).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn monitor_get_preferred(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
+    Ok(preferred_monitor(&app_handle))
+}
+
+#[tauri::command]
+pub async fn monitor_set_preferred(
+    app_handle: tauri::AppHandle,
+    monitor: Option<String>,
+) -> Result<(), String> {
+    // SECURITY: Validate input parameters
+    if let Some(monitor) = &monitor {
+        crate::security::validate_user_input(monitor, "monitor", 256)
+            .map_err(|e| format!("Invalid monitor: {}", e))?;
+    }
+
+    let store = app_handle
+        .store(SETTINGS_STORE_NAME)
+        .map_err(|e| e.to_string())?;
+    match monitor {
+        Some(monitor) => store.set(PREFERRED_MONITOR_FIELD, monitor),
+        None => {
+            store.delete(PREFERRED_MONITOR_FIELD);
+        }
+    }
+    store.save().map_err(|e| e.to_string())?;
+
+    Ok(())
+}